@@ -0,0 +1,114 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_xpbd_3d::prelude::*;
+
+use crate::layers::CollisionMatrix;
+use crate::projectile::FireAt;
+use crate::projectile::Launcher;
+use crate::State;
+
+/// Fires every [`Launcher`] towards the point under the cursor on a left
+/// click, and toggles their auto-fire with a key.
+///
+/// The point is where the ray through the cursor first hits a collider on
+/// `layer`, or else crosses the z = 0 plane the launchers and the rock sit
+/// in. Needs a window and mouse, so it isn't part of
+/// [`ShootingRangePlugin`](crate::ShootingRangePlugin).
+#[derive(Clone, Debug)]
+pub struct AimPlugin {
+    /// A layer name from the [`CollisionMatrix`].
+    pub layer: String,
+    pub toggle_auto_fire: KeyCode,
+}
+
+impl Default for AimPlugin {
+    fn default() -> Self {
+        Self {
+            layer: "Object".into(),
+            toggle_auto_fire: KeyCode::KeyF,
+        }
+    }
+}
+
+impl Plugin for AimPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(AimSettings {
+            layer: self.layer.clone(),
+            toggle_auto_fire: self.toggle_auto_fire,
+        })
+        .add_systems(
+            Update,
+            (aim_with_mouse, toggle_auto_fire).run_if(in_state(State::Play)),
+        );
+    }
+}
+
+#[derive(Resource, Clone, Debug)]
+pub struct AimSettings {
+    pub layer: String,
+    pub toggle_auto_fire: KeyCode,
+}
+
+/// The point aimed at along `ray`: `hit_distance` along it if it hit
+/// something, or where it crosses the z = 0 plane.
+pub fn aim_point(ray: Ray3d, hit_distance: Option<f32>) -> Option<Vec3> {
+    let distance =
+        hit_distance.or_else(|| ray.intersect_plane(Vec3::ZERO, Plane3d::new(Vec3::Z)))?;
+    Some(ray.get_point(distance))
+}
+
+fn aim_with_mouse(
+    buttons: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    spatial: SpatialQuery,
+    matrix: Res<CollisionMatrix>,
+    settings: Res<AimSettings>,
+    mut fire: EventWriter<FireAt>,
+) {
+    if !buttons.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let Some(cursor) = windows.get_single().ok().and_then(Window::cursor_position) else {
+        return;
+    };
+    let Some(ray) = cameras
+        .iter()
+        .filter(|(camera, _)| camera.is_active)
+        .find_map(|(camera, transform)| camera.viewport_to_world(transform, cursor))
+    else {
+        return;
+    };
+    let hit = matrix.mask(&settings.layer).and_then(|mask| {
+        spatial.cast_ray(
+            ray.origin,
+            ray.direction,
+            f32::MAX,
+            true,
+            SpatialQueryFilter::from_mask(mask),
+        )
+    });
+    if let Some(target) = aim_point(ray, hit.map(|hit| hit.time_of_impact)) {
+        fire.send(FireAt {
+            launcher: None,
+            target,
+        });
+    }
+}
+
+fn toggle_auto_fire(
+    keys: Res<ButtonInput<KeyCode>>,
+    settings: Res<AimSettings>,
+    mut launchers: Query<&mut Launcher>,
+) {
+    if !keys.just_pressed(settings.toggle_auto_fire) {
+        return;
+    }
+    for mut launcher in &mut launchers {
+        launcher.auto_fire = !launcher.auto_fire;
+        info!(
+            "auto-fire {}",
+            if launcher.auto_fire { "on" } else { "off" }
+        );
+    }
+}
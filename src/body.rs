@@ -0,0 +1,149 @@
+use bevy::ecs::system::EntityCommand;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy::utils::HashSet;
+use bevy_xpbd_3d::prelude::*;
+use serde::Deserialize;
+
+/// Keeps the mass of bodies with a [`BodyMass`] at the requested value.
+pub struct BodyPlugin;
+
+impl Plugin for BodyPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<BodySettings>()
+            .register_type::<BodyType>()
+            .register_type::<BodyMass>()
+            .register_type::<BaseDensity>()
+            .add_systems(PostUpdate, fit_body_mass.before(PhysicsSet::Prepare));
+    }
+}
+
+/// How a rigid body moves and responds to collisions. Apply it to a rigid
+/// body entity as an [`EntityCommand`].
+///
+/// Restitution and friction are used by every collider of the body that
+/// doesn't set its own, e.g. through [`NodeExtras`](crate::extras::NodeExtras).
+#[derive(Reflect, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(default)]
+pub struct BodySettings {
+    pub body_type: BodyType,
+    /// Mass of the body. Without it the mass follows from the volume and
+    /// density of its colliders.
+    pub mass: Option<f32>,
+    pub restitution: f32,
+    pub friction: f32,
+    pub linear_damping: f32,
+    pub angular_damping: f32,
+}
+
+impl Default for BodySettings {
+    fn default() -> Self {
+        Self {
+            body_type: BodyType::default(),
+            mass: None,
+            restitution: 0.3,
+            friction: 0.3,
+            linear_damping: 0.0,
+            angular_damping: 0.0,
+        }
+    }
+}
+
+impl BodySettings {
+    pub fn rigid_body(&self) -> RigidBody {
+        match self.body_type {
+            BodyType::Kinematic => RigidBody::Kinematic,
+            BodyType::Dynamic => RigidBody::Dynamic,
+            BodyType::Static => RigidBody::Static,
+        }
+    }
+}
+
+/// The [`RigidBody`] a [`BodySettings`] makes, as written in scenarios.
+#[derive(Reflect, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BodyType {
+    /// Moved only by its velocity.
+    #[default]
+    Kinematic,
+    /// Moved by gravity and collisions.
+    Dynamic,
+    /// Never moves.
+    Static,
+}
+
+impl EntityCommand for BodySettings {
+    fn apply(self, entity: Entity, world: &mut World) {
+        let Some(mut body) = world.get_entity_mut(entity) else {
+            return;
+        };
+        body.insert((
+            self.rigid_body(),
+            Restitution::new(self.restitution),
+            Friction::new(self.friction),
+            LinearDamping(self.linear_damping),
+            AngularDamping(self.angular_damping),
+        ));
+        match self.mass {
+            Some(mass) => body.insert(BodyMass(mass)),
+            None => body.remove::<BodyMass>(),
+        };
+    }
+}
+
+/// Mass a body is brought to by scaling the density of its colliders, which
+/// keeps their proportions and the body's inertia consistent. Without it, the
+/// colliders are back at their [`BaseDensity`].
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Component)]
+pub struct BodyMass(pub f32);
+
+/// Density of a collider as authored, which [`BodyMass`] scales from.
+/// Colliders without one have the default density.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Component)]
+pub struct BaseDensity(pub f32);
+
+type MassChanges<'w, 's> = Query<'w, 's, Entity, Or<(Changed<BodyMass>, Changed<Mass>)>>;
+
+type BodyColliders<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static ColliderParent,
+        &'static Collider,
+        Option<&'static BaseDensity>,
+        &'static mut ColliderDensity,
+    ),
+>;
+
+fn fit_body_mass(
+    changed: MassChanges,
+    mut released: RemovedComponents<BodyMass>,
+    targets: Query<&BodyMass>,
+    mut colliders: BodyColliders,
+) {
+    let bodies: HashSet<Entity> = changed.iter().chain(released.read()).collect();
+    if bodies.is_empty() {
+        return;
+    }
+    let base_density =
+        |base: Option<&BaseDensity>| base.map_or(ColliderDensity::default().0, |b| b.0);
+    // Masses at the base densities, so fitting never builds on an earlier fit.
+    let mut base_masses: HashMap<Entity, f32> = HashMap::new();
+    for (parent, collider, base, _) in &colliders {
+        if bodies.contains(&parent.get()) {
+            let mass = collider.mass_properties(base_density(base)).mass.0;
+            *base_masses.entry(parent.get()).or_default() += mass;
+        }
+    }
+    for (parent, _, base, mut density) in &mut colliders {
+        let Some(&base_mass) = base_masses.get(&parent.get()) else {
+            continue;
+        };
+        let scale = match targets.get(parent.get()) {
+            Ok(target) if base_mass.is_finite() && base_mass > 0.0 => target.0 / base_mass,
+            _ => 1.0,
+        };
+        density.set_if_neq(ColliderDensity(base_density(base) * scale));
+    }
+}
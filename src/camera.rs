@@ -0,0 +1,256 @@
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::input::mouse::MouseMotion;
+use bevy::input::mouse::MouseScrollUnit;
+use bevy::input::mouse::MouseWheel;
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+use serde::Deserialize;
+
+use crate::pool::Parked;
+use crate::projectile::FiredAt;
+use crate::projectile::Projectile;
+
+/// Drives cameras carrying an [`OrbitCamera`] from the mouse and keyboard:
+/// dragging with `orbit_button` orbits, with `pan_button` pans, and the wheel
+/// zooms. `frame_key` fits the view to the [`CameraTarget`]s, and `follow_key`
+/// toggles following the latest projectile.
+///
+/// Needs window input, so it isn't part of
+/// [`ShootingRangePlugin`](crate::ShootingRangePlugin).
+#[derive(Clone, Debug)]
+pub struct CameraControlPlugin {
+    /// Radians turned per pixel dragged.
+    pub orbit_sensitivity: f32,
+    /// The left button is taken by aiming.
+    pub orbit_button: MouseButton,
+    pub pan_button: MouseButton,
+    /// How much one line of scrolling shrinks the distance to the focus.
+    pub zoom_step: f32,
+    pub frame_key: KeyCode,
+    pub follow_key: KeyCode,
+}
+
+impl Default for CameraControlPlugin {
+    fn default() -> Self {
+        Self {
+            orbit_sensitivity: 0.005,
+            orbit_button: MouseButton::Right,
+            pan_button: MouseButton::Middle,
+            zoom_step: 0.1,
+            frame_key: KeyCode::Period,
+            follow_key: KeyCode::KeyT,
+        }
+    }
+}
+
+impl Plugin for CameraControlPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<OrbitCamera>()
+            .insert_resource(CameraControls(self.clone()))
+            .add_systems(
+                Update,
+                (
+                    (drag_camera, frame_target, follow_projectile),
+                    place_orbit_cameras,
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[derive(Resource, Deref)]
+struct CameraControls(CameraControlPlugin);
+
+/// Places a camera on a sphere around `focus`, looking at it.
+///
+/// With a zero `yaw` and `pitch` the camera looks down -Z, and a positive
+/// pitch lifts it above the focus.
+#[derive(Component, Reflect, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[reflect(Component)]
+#[serde(default)]
+pub struct OrbitCamera {
+    pub focus: Vec3,
+    pub distance: f32,
+    /// Radians around +Y.
+    pub yaw: f32,
+    /// Radians above the horizon, kept short of the poles.
+    pub pitch: f32,
+    /// Keep the latest projectile in focus.
+    pub follow: bool,
+}
+
+impl Default for OrbitCamera {
+    fn default() -> Self {
+        Self {
+            focus: Vec3::ZERO,
+            distance: 100.0,
+            yaw: 0.0,
+            pitch: 0.0,
+            follow: false,
+        }
+    }
+}
+
+/// How close zooming gets to the focus.
+const MIN_DISTANCE: f32 = 0.1;
+/// How close orbiting gets to looking straight up or down.
+const MAX_PITCH: f32 = FRAC_PI_2 - 0.01;
+
+impl OrbitCamera {
+    pub fn rotation(&self) -> Quat {
+        Quat::from_rotation_y(self.yaw) * Quat::from_rotation_x(-self.pitch)
+    }
+
+    pub fn transform(&self) -> Transform {
+        let rotation = self.rotation();
+        Transform::from_translation(self.focus + rotation * Vec3::Z * self.distance)
+            .with_rotation(rotation)
+    }
+
+    /// Turns around the focus by `angles` radians of yaw and pitch.
+    pub fn orbit(&mut self, angles: Vec2) {
+        self.yaw += angles.x;
+        self.pitch = (self.pitch + angles.y).clamp(-MAX_PITCH, MAX_PITCH);
+    }
+
+    /// Moves the focus so that it follows a drag of `pixels` across a
+    /// viewport `viewport_height` pixels tall with a vertical `fov`.
+    pub fn pan(&mut self, pixels: Vec2, viewport_height: f32, fov: f32) {
+        if viewport_height <= 0.0 {
+            return;
+        }
+        let units_per_pixel = 2.0 * self.distance * (fov / 2.0).tan() / viewport_height;
+        // Screen y grows downwards.
+        let offset = Vec3::new(-pixels.x, pixels.y, 0.0) * units_per_pixel;
+        self.focus += self.rotation() * offset;
+    }
+
+    /// Scales the distance to the focus by `factor`.
+    pub fn zoom(&mut self, factor: f32) {
+        self.distance = (self.distance * factor).max(MIN_DISTANCE);
+    }
+
+    /// Focuses on the box from `min` to `max` and backs off until its
+    /// bounding sphere fits the `projection`, whichever way it is viewed.
+    pub fn frame(&mut self, min: Vec3, max: Vec3, projection: &PerspectiveProjection) {
+        let radius = (max - min).length() / 2.0;
+        let horizontal = 2.0 * ((projection.fov / 2.0).tan() * projection.aspect_ratio).atan();
+        let fov = projection.fov.min(horizontal);
+        self.focus = (min + max) / 2.0;
+        self.distance = (radius / (fov / 2.0).sin()).max(MIN_DISTANCE);
+    }
+}
+
+/// Marks the entities an [`OrbitCamera`] frames, such as the rock.
+#[derive(Component, Default, Debug)]
+pub struct CameraTarget;
+
+fn drag_camera(
+    controls: Res<CameraControls>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    mut motion: EventReader<MouseMotion>,
+    mut wheel: EventReader<MouseWheel>,
+    mut cameras: Query<(&mut OrbitCamera, &Camera, &Projection)>,
+) {
+    let drag: Vec2 = motion.read().map(|event| event.delta).sum();
+    let lines: f32 = wheel
+        .read()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            // Roughly a line per 16 pixels, as browsers do.
+            MouseScrollUnit::Pixel => event.y / 16.0,
+        })
+        .sum();
+    for (mut orbit, camera, projection) in &mut cameras {
+        if buttons.pressed(controls.orbit_button) {
+            orbit.orbit(-drag * controls.orbit_sensitivity);
+        }
+        if buttons.pressed(controls.pan_button) {
+            let height = camera.logical_viewport_size().map_or(0.0, |size| size.y);
+            let fov = match projection {
+                Projection::Perspective(perspective) => perspective.fov,
+                Projection::Orthographic(_) => continue,
+            };
+            orbit.pan(drag, height, fov);
+        }
+        if lines != 0.0 {
+            orbit.zoom((1.0 - controls.zoom_step).powf(lines));
+        }
+    }
+}
+
+fn frame_target(
+    controls: Res<CameraControls>,
+    keys: Res<ButtonInput<KeyCode>>,
+    targets: Query<(Entity, &GlobalTransform), With<CameraTarget>>,
+    colliders: Query<(&ColliderParent, &ColliderAabb)>,
+    mut cameras: Query<(&mut OrbitCamera, &Projection)>,
+) {
+    if !keys.just_pressed(controls.frame_key) {
+        return;
+    }
+    // Bodies without colliders are framed as a unit cube.
+    let bounds = targets.iter().map(|(target, transform)| {
+        colliders
+            .iter()
+            .filter(|(parent, _)| parent.get() == target)
+            .map(|(_, aabb)| (aabb.min, aabb.max))
+            .reduce(union)
+            .unwrap_or_else(|| {
+                let center = transform.translation();
+                (center - 0.5, center + 0.5)
+            })
+    });
+    let Some((min, max)) = bounds.reduce(union) else {
+        return;
+    };
+    for (mut orbit, projection) in &mut cameras {
+        if let Projection::Perspective(perspective) = projection {
+            orbit.follow = false;
+            orbit.frame(min, max, perspective);
+        }
+    }
+}
+
+fn union((min1, max1): (Vec3, Vec3), (min2, max2): (Vec3, Vec3)) -> (Vec3, Vec3) {
+    (min1.min(min2), max1.max(max2))
+}
+
+type Projectiles<'w, 's> = Query<
+    'w,
+    's,
+    (&'static GlobalTransform, &'static FiredAt),
+    (With<Projectile>, Without<Parked>),
+>;
+
+fn follow_projectile(
+    controls: Res<CameraControls>,
+    keys: Res<ButtonInput<KeyCode>>,
+    projectiles: Projectiles,
+    mut cameras: Query<&mut OrbitCamera>,
+) {
+    // The latest projectile is the one fired last, whatever its lifetime.
+    let latest = projectiles
+        .iter()
+        .max_by(|(_, a), (_, b)| a.0.total_cmp(&b.0))
+        .map(|(transform, _)| transform.translation());
+    for mut orbit in &mut cameras {
+        if keys.just_pressed(controls.follow_key) {
+            orbit.follow = !orbit.follow;
+            info!(
+                "follow projectile {}",
+                if orbit.follow { "on" } else { "off" }
+            );
+        }
+        if let (true, Some(focus)) = (orbit.follow, latest) {
+            orbit.focus = focus;
+        }
+    }
+}
+
+fn place_orbit_cameras(mut cameras: Query<(&OrbitCamera, &mut Transform), Changed<OrbitCamera>>) {
+    for (orbit, mut transform) in &mut cameras {
+        *transform = orbit.transform();
+    }
+}
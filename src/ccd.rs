@@ -0,0 +1,174 @@
+use bevy::prelude::*;
+use bevy_xpbd_3d::plugins::collision::contact_reporting::report_contacts;
+use bevy_xpbd_3d::prelude::*;
+use bevy_xpbd_3d::PhysicsSchedule;
+use bevy_xpbd_3d::PhysicsStepSet;
+
+use crate::layers::CollisionMatrix;
+use crate::pool::Parked;
+use crate::projectile::Projectile;
+
+/// Continuous collision detection for [`Projectile`]s, so fast ones don't
+/// tunnel through thin colliders between physics steps.
+///
+/// Before every step, each projectile collider is shape cast along its body's
+/// velocity against colliders on `layer`. Hits the step misses are reported
+/// like the ones it finds: a [`Collision`] and a [`CollisionStarted`] event,
+/// and a [`CollisionEnded`] event after the next step.
+///
+/// Runs in the [`PhysicsSchedule`], so add it after
+/// [`PhysicsPlugins`](bevy_xpbd_3d::plugins::PhysicsPlugins).
+#[derive(Clone, Debug)]
+pub struct CcdPlugin {
+    /// A layer name from the [`CollisionMatrix`].
+    pub layer: String,
+}
+
+impl Default for CcdPlugin {
+    fn default() -> Self {
+        Self {
+            layer: "Object".into(),
+        }
+    }
+}
+
+impl Plugin for CcdPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(CcdSettings {
+            layer: self.layer.clone(),
+        })
+        .init_resource::<SweptContacts>()
+        .add_systems(
+            PhysicsSchedule,
+            (
+                sweep_projectiles.before(PhysicsStepSet::BroadPhase),
+                report_swept_contacts
+                    .in_set(PhysicsStepSet::ReportContacts)
+                    .after(report_contacts),
+            ),
+        );
+    }
+}
+
+#[derive(Resource, Clone, Debug)]
+pub struct CcdSettings {
+    pub layer: String,
+}
+
+/// Contacts found by sweeping, waiting to be compared with the step's own.
+#[derive(Resource, Default)]
+struct SweptContacts {
+    found: Vec<Contacts>,
+    /// Pairs reported in the last step, which end in this one.
+    reported: Vec<(Entity, Entity)>,
+}
+
+type SweptColliders<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Collider,
+        &'static Position,
+        &'static Rotation,
+        &'static ColliderParent,
+        &'static CollisionLayers,
+    ),
+>;
+
+fn sweep_projectiles(
+    time: Res<Time>,
+    spatial: SpatialQuery,
+    colliders: SweptColliders,
+    projectiles: Query<&LinearVelocity, (With<Projectile>, Without<Parked>)>,
+    matrix: Res<CollisionMatrix>,
+    settings: Res<CcdSettings>,
+    mut swept: ResMut<SweptContacts>,
+) {
+    let Some(target) = matrix.mask(&settings.layer) else {
+        return;
+    };
+    // The physics schedule sets `Time` to the step being taken.
+    let delta = time.delta_seconds();
+    for (entity, collider, position, rotation, parent, layers) in &colliders {
+        let Ok(velocity) = projectiles.get(parent.get()) else {
+            continue;
+        };
+        let Ok(direction) = Direction3d::new(velocity.0) else {
+            continue;
+        };
+        let mask = target & layers.filters;
+        if mask == LayerMask::NONE {
+            continue;
+        }
+        // Colliders the projectile already overlaps are the step's business.
+        let Some(hit) = spatial.cast_shape(
+            collider,
+            position.0,
+            rotation.0,
+            direction,
+            velocity.length() * delta,
+            true,
+            SpatialQueryFilter::from_mask(mask).with_excluded_entities([entity]),
+        ) else {
+            continue;
+        };
+        swept.found.push(Contacts {
+            entity1: entity,
+            entity2: hit.entity,
+            manifolds: vec![ContactManifold {
+                contacts: vec![ContactData::new(
+                    hit.point2,
+                    hit.point1,
+                    hit.normal2,
+                    hit.normal1,
+                    0.0,
+                    0,
+                )],
+                normal1: hit.normal2,
+                normal2: hit.normal1,
+                index: 0,
+            }],
+            during_current_frame: true,
+            during_current_substep: false,
+            during_previous_frame: false,
+            total_normal_impulse: 0.0,
+            total_tangent_impulse: 0.0,
+        });
+    }
+}
+
+fn report_swept_contacts(
+    collisions: Res<Collisions>,
+    parents: Query<&ColliderParent>,
+    mut swept: ResMut<SweptContacts>,
+    mut collision: EventWriter<Collision>,
+    mut started: EventWriter<CollisionStarted>,
+    mut ended: EventWriter<CollisionEnded>,
+) {
+    for (entity1, entity2) in std::mem::take(&mut swept.reported) {
+        ended.send(CollisionEnded(entity1, entity2));
+    }
+    let body = |collider| parents.get(collider).ok().map(ColliderParent::get);
+    for contacts in std::mem::take(&mut swept.found) {
+        // The step may have caught the hit itself, possibly against another
+        // collider of the same body.
+        let target = body(contacts.entity2);
+        let caught = collisions
+            .collisions_with_entity(contacts.entity1)
+            .filter(|c| c.during_current_frame)
+            .any(|c| {
+                let other = match c.entity1 == contacts.entity1 {
+                    true => c.entity2,
+                    false => c.entity1,
+                };
+                body(other) == target
+            });
+        if caught {
+            continue;
+        }
+        swept.reported.push((contacts.entity1, contacts.entity2));
+        started.send(CollisionStarted(contacts.entity1, contacts.entity2));
+        collision.send(Collision(contacts));
+    }
+}
@@ -0,0 +1,286 @@
+use std::fmt;
+
+use bevy::ecs::system::EntityCommand;
+use bevy::prelude::*;
+use bevy::render::primitives::Aabb;
+use bevy::utils::HashMap;
+use bevy_xpbd_3d::components::CollisionLayers;
+use bevy_xpbd_3d::prelude::Collider;
+use bevy_xpbd_3d::prelude::VHACDParameters;
+
+use crate::layers::CollisionLayerName;
+use crate::layers::CollisionMatrix;
+
+/// Reports [`Collidable`] failures, retries colliders whose mesh was not
+/// loaded yet and keeps the [`ColliderCache`] in sync with mesh assets.
+pub struct CollidablePlugin;
+
+impl Plugin for CollidablePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ColliderBuildFailed>()
+            .init_resource::<ColliderCache>()
+            .add_systems(Update, (invalidate_collider_cache, retry_pending_colliders));
+    }
+}
+
+/// Builds a [`Collider`] from the mesh of the entity's first child using the
+/// given [`ColliderStrategy`] and puts it on the named layer of the
+/// [`CollisionMatrix`].
+///
+/// If the mesh is not loaded yet the entity gets a [`PendingCollidable`] and
+/// the collider is built once the mesh arrives. Any other problem is logged
+/// and reported as a [`ColliderBuildFailed`] event.
+#[derive(Clone, Debug)]
+pub struct Collidable {
+    pub layer: String,
+    pub strategy: ColliderStrategy,
+}
+
+impl Collidable {
+    fn build(
+        &self,
+        entity: Entity,
+        world: &mut World,
+    ) -> Result<(Collider, CollisionLayers), CollidableError> {
+        let layers = world
+            .get_resource::<CollisionMatrix>()
+            .and_then(|matrix| matrix.layers(&self.layer))
+            .ok_or_else(|| CollidableError::UnknownLayer(self.layer.clone()))?;
+        let children = world
+            .get::<Children>(entity)
+            .ok_or(CollidableError::NoChildren)?;
+        let mesh_id = children
+            .iter()
+            .find_map(|&child| world.get::<Handle<Mesh>>(child))
+            .ok_or(CollidableError::NoMesh)?
+            .id();
+        if let Some(collider) = world
+            .get_resource::<ColliderCache>()
+            .and_then(|cache| cache.get(mesh_id, &self.strategy))
+        {
+            return Ok((collider.clone(), layers));
+        }
+        let meshes = world
+            .get_resource::<Assets<Mesh>>()
+            .ok_or(CollidableError::NoMeshAssets)?;
+        let mesh = meshes
+            .get(mesh_id)
+            .ok_or(CollidableError::MeshNotLoaded(mesh_id))?;
+        let collider = self
+            .strategy
+            .build(mesh)
+            .ok_or(CollidableError::InvalidMesh(mesh_id))?;
+        if let Some(mut cache) = world.get_resource_mut::<ColliderCache>() {
+            cache.insert(mesh_id, self.strategy.clone(), collider.clone());
+        }
+        Ok((collider, layers))
+    }
+}
+
+/// Colliders already built by [`Collidable`], per mesh and [`ColliderStrategy`].
+///
+/// Cloning a [`Collider`] shares its shape, so every entity built from the
+/// same mesh and strategy reuses one shape. Entries are dropped when their
+/// mesh is modified or removed.
+#[derive(Resource, Default)]
+pub struct ColliderCache {
+    colliders: HashMap<AssetId<Mesh>, Vec<(ColliderStrategy, Collider)>>,
+}
+
+impl ColliderCache {
+    pub fn get(&self, mesh: AssetId<Mesh>, strategy: &ColliderStrategy) -> Option<&Collider> {
+        self.colliders
+            .get(&mesh)?
+            .iter()
+            .find_map(|(s, collider)| (s == strategy).then_some(collider))
+    }
+
+    pub fn insert(&mut self, mesh: AssetId<Mesh>, strategy: ColliderStrategy, collider: Collider) {
+        let entries = self.colliders.entry(mesh).or_default();
+        entries.retain(|(s, _)| *s != strategy);
+        entries.push((strategy, collider));
+    }
+
+    /// Forgets every collider built from `mesh`.
+    pub fn invalidate(&mut self, mesh: AssetId<Mesh>) {
+        self.colliders.remove(&mesh);
+    }
+
+    /// The number of cached colliders.
+    pub fn len(&self) -> usize {
+        self.colliders.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.colliders.is_empty()
+    }
+}
+
+fn invalidate_collider_cache(
+    mut mesh_events: EventReader<AssetEvent<Mesh>>,
+    mut cache: ResMut<ColliderCache>,
+) {
+    for event in mesh_events.read() {
+        if let AssetEvent::Modified { id } | AssetEvent::Removed { id } = event {
+            cache.invalidate(*id);
+        }
+    }
+}
+
+/// How a [`Collidable`] turns a mesh into a [`Collider`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum ColliderStrategy {
+    /// The exact triangles of the mesh. Precise but expensive, and hollow for
+    /// dynamic bodies.
+    #[default]
+    Trimesh,
+    /// The convex hull of the mesh vertices.
+    ConvexHull,
+    /// A set of convex parts approximating the mesh.
+    ConvexDecomposition(VHACDParameters),
+    /// A sphere at the center of the mesh bounding box, as wide as its
+    /// longest axis.
+    Sphere,
+    /// A capsule spanning the longest axis of the mesh bounding box, as wide
+    /// as the longer of the two other axes.
+    Capsule,
+    /// The mesh bounding box.
+    Cuboid,
+}
+
+impl ColliderStrategy {
+    pub fn build(&self, mesh: &Mesh) -> Option<Collider> {
+        match self {
+            ColliderStrategy::Trimesh => Collider::trimesh_from_mesh(mesh),
+            ColliderStrategy::ConvexHull => Collider::convex_hull_from_mesh(mesh),
+            ColliderStrategy::ConvexDecomposition(parameters) => {
+                Collider::convex_decomposition_from_mesh_with_config(mesh, parameters)
+            }
+            ColliderStrategy::Sphere => mesh.compute_aabb().map(|aabb| fit_sphere(&aabb)),
+            ColliderStrategy::Capsule => mesh.compute_aabb().map(|aabb| fit_capsule(&aabb)),
+            ColliderStrategy::Cuboid => mesh.compute_aabb().map(|aabb| fit_cuboid(&aabb)),
+        }
+    }
+}
+
+fn fit_sphere(aabb: &Aabb) -> Collider {
+    let radius = aabb.half_extents.max_element();
+    offset(Collider::sphere(radius), aabb.center.into())
+}
+
+fn fit_capsule(aabb: &Aabb) -> Collider {
+    let half_extents = Vec3::from(aabb.half_extents);
+    let (axis, half_length, radius) = if half_extents.x >= half_extents.y.max(half_extents.z) {
+        (Vec3::X, half_extents.x, half_extents.y.max(half_extents.z))
+    } else if half_extents.y >= half_extents.z {
+        (Vec3::Y, half_extents.y, half_extents.x.max(half_extents.z))
+    } else {
+        (Vec3::Z, half_extents.z, half_extents.x.max(half_extents.y))
+    };
+    let half_segment = axis * (half_length - radius).max(0.0);
+    let center = Vec3::from(aabb.center);
+    Collider::capsule_endpoints(center - half_segment, center + half_segment, radius)
+}
+
+fn fit_cuboid(aabb: &Aabb) -> Collider {
+    let size = Vec3::from(aabb.half_extents) * 2.0;
+    offset(Collider::cuboid(size.x, size.y, size.z), aabb.center.into())
+}
+
+fn offset(collider: Collider, center: Vec3) -> Collider {
+    if center == Vec3::ZERO {
+        collider
+    } else {
+        Collider::compound(vec![(center, Quat::IDENTITY, collider)])
+    }
+}
+
+impl EntityCommand for Collidable {
+    fn apply(self, entity: Entity, world: &mut World) {
+        match self.build(entity, world) {
+            Ok((collider, layers)) => {
+                world
+                    .entity_mut(entity)
+                    .insert((collider, layers, CollisionLayerName(self.layer)))
+                    .remove::<PendingCollidable>();
+            }
+            Err(CollidableError::MeshNotLoaded(mesh)) => {
+                debug!("collider for {entity:?} waits for mesh {mesh:?}");
+                world.entity_mut(entity).insert(PendingCollidable {
+                    collidable: self,
+                    mesh,
+                });
+            }
+            Err(reason) => {
+                warn!("cannot build collider for {entity:?}: {reason}");
+                world.send_event(ColliderBuildFailed { entity, reason });
+            }
+        }
+    }
+}
+
+/// Why a [`Collidable`] could not build its collider.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CollidableError {
+    /// The [`CollisionMatrix`] has no layer with this name.
+    UnknownLayer(String),
+    /// The entity has no children to take a mesh from.
+    NoChildren,
+    /// None of the entity's children has a mesh.
+    NoMesh,
+    /// The `Assets<Mesh>` resource does not exist.
+    NoMeshAssets,
+    /// The mesh is not loaded (yet).
+    MeshNotLoaded(AssetId<Mesh>),
+    /// The mesh has no usable positions or indices for the chosen strategy.
+    InvalidMesh(AssetId<Mesh>),
+}
+
+impl fmt::Display for CollidableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CollidableError::UnknownLayer(name) => write!(f, "unknown collision layer {name:?}"),
+            CollidableError::NoChildren => write!(f, "entity has no children"),
+            CollidableError::NoMesh => write!(f, "no child has a mesh"),
+            CollidableError::NoMeshAssets => write!(f, "mesh assets are not available"),
+            CollidableError::MeshNotLoaded(id) => write!(f, "mesh {id:?} is not loaded"),
+            CollidableError::InvalidMesh(id) => {
+                write!(f, "mesh {id:?} cannot be turned into a collider")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CollidableError {}
+
+/// Sent when a [`Collidable`] fails for a reason other than a mesh that is
+/// still loading.
+#[derive(Event, Clone, Debug)]
+pub struct ColliderBuildFailed {
+    pub entity: Entity,
+    pub reason: CollidableError,
+}
+
+/// A [`Collidable`] waiting for its mesh to be loaded.
+#[derive(Component, Debug)]
+pub struct PendingCollidable {
+    pub collidable: Collidable,
+    pub mesh: AssetId<Mesh>,
+}
+
+fn retry_pending_colliders(
+    mut mesh_events: EventReader<AssetEvent<Mesh>>,
+    pending: Query<(Entity, &PendingCollidable)>,
+    mut commands: Commands,
+) {
+    for event in mesh_events.read() {
+        let (AssetEvent::Added { id } | AssetEvent::LoadedWithDependencies { id }) = event else {
+            continue;
+        };
+        for (entity, pending) in &pending {
+            if pending.mesh == *id {
+                commands.entity(entity).add(pending.collidable.clone());
+            }
+        }
+    }
+}
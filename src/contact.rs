@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::Contacts;
+
+/// All contact points between two colliders reduced to a single impact.
+///
+/// Points and normals are in the local space of the respective collider, like
+/// in [`ContactData`](bevy_xpbd_3d::prelude::ContactData). Points are weighted
+/// by penetration depth, so the deepest contacts dominate; if nothing
+/// penetrates, every point counts the same.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ContactSummary {
+    pub point1: Vec3,
+    pub point2: Vec3,
+    /// Average contact normal, or zero if the normals cancel out.
+    pub normal1: Vec3,
+    pub normal2: Vec3,
+    pub max_penetration: f32,
+    /// Contact points over all manifolds.
+    pub count: usize,
+}
+
+impl ContactSummary {
+    /// Summarizes `contacts`, or returns `None` if no manifold holds any point.
+    pub fn new(contacts: &Contacts) -> Option<Self> {
+        let points = || contacts.manifolds.iter().flat_map(|m| &m.contacts);
+        let count = points().count();
+        if count == 0 {
+            return None;
+        }
+        let max_penetration = points()
+            .map(|c| c.penetration)
+            .fold(f32::NEG_INFINITY, f32::max);
+        let total_depth: f32 = points().map(|c| c.penetration.max(0.0)).sum();
+        let weight = |penetration: f32| {
+            if total_depth > f32::EPSILON {
+                penetration.max(0.0) / total_depth
+            } else {
+                1.0 / count as f32
+            }
+        };
+        Some(Self {
+            point1: points().map(|c| c.point1 * weight(c.penetration)).sum(),
+            point2: points().map(|c| c.point2 * weight(c.penetration)).sum(),
+            normal1: points()
+                .map(|c| c.normal1)
+                .sum::<Vec3>()
+                .normalize_or_zero(),
+            normal2: points()
+                .map(|c| c.normal2)
+                .sum::<Vec3>()
+                .normalize_or_zero(),
+            max_penetration,
+            count,
+        })
+    }
+
+    /// `point1` and `point2` in world space, given the colliders' transforms.
+    pub fn world_points(
+        &self,
+        transform1: &GlobalTransform,
+        transform2: &GlobalTransform,
+    ) -> (Vec3, Vec3) {
+        let (t1, t2) = (
+            transform1.compute_transform(),
+            transform2.compute_transform(),
+        );
+        (
+            t1.translation + t1.rotation * self.point1,
+            t2.translation + t2.rotation * self.point2,
+        )
+    }
+}
@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+
+use crate::pool::park;
+use crate::pool::Pooled;
+use crate::State;
+
+/// Despawns entities carrying a [`DelayedDespawn`] once their timer runs out,
+/// or parks them if they are [`Pooled`]. Timers are frozen while
+/// [`State::Paused`].
+pub struct DespawnPlugin;
+
+impl Plugin for DespawnPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, despawn_delayed.run_if(not(in_state(State::Paused))));
+    }
+}
+
+#[derive(Component)]
+pub struct DelayedDespawn {
+    timer: Timer,
+}
+
+impl DelayedDespawn {
+    pub fn after(seconds: f32) -> DelayedDespawn {
+        DelayedDespawn {
+            timer: Timer::from_seconds(seconds, TimerMode::Once),
+        }
+    }
+
+    /// How much of the delay is left, from 1 down to 0.
+    pub fn fraction_remaining(&self) -> f32 {
+        self.timer.fraction_remaining()
+    }
+}
+
+fn despawn_delayed(
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut DelayedDespawn, Has<Pooled>)>,
+    mut commands: Commands,
+) {
+    for (entity, mut dd, pooled) in query.iter_mut() {
+        if !dd.timer.tick(time.delta()).finished() {
+            continue;
+        }
+        if pooled {
+            commands.entity(entity).remove::<DelayedDespawn>().add(park);
+        } else {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use bevy::ecs::system::EntityCommands;
+use bevy::gltf::GltfExtras;
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+use serde::Deserialize;
+
+use crate::body::BaseDensity;
+use crate::collidable::Collidable;
+use crate::collidable::ColliderStrategy;
+use crate::fracture::Fracturable;
+
+/// Node settings authored as glTF extras, i.e. custom properties in Blender.
+///
+/// ```json
+/// {"collider": "convex", "layer": "Object", "hidden": true, "fracture": 8}
+/// {"collider": "capsule", "density": 2.0, "restitution": 0.6, "friction": 0.4}
+/// ```
+///
+/// `collider` is one of `trimesh`, `convex`, `decomposition`, `sphere`,
+/// `capsule` or `cuboid` and defaults to `trimesh` when only a `layer` is
+/// given. A node gets a [`Collidable`] as soon as it has a `layer`, and
+/// breaks into `fracture` pieces of debris when its body is destroyed.
+///
+/// `density`, `restitution` and `friction` describe the node's collider and
+/// take precedence over the [`BodySettings`](crate::body::BodySettings) of
+/// its body, so each scene can carry its own material.
+#[derive(Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(default)]
+pub struct NodeExtras {
+    pub collider: Option<String>,
+    /// A layer name from the [`CollisionMatrix`](crate::layers::CollisionMatrix).
+    pub layer: Option<String>,
+    pub hidden: bool,
+    pub fracture: Option<usize>,
+    pub density: Option<f32>,
+    pub restitution: Option<f32>,
+    pub friction: Option<f32>,
+}
+
+impl NodeExtras {
+    pub fn parse(extras: &GltfExtras) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(&extras.value)
+    }
+
+    pub fn strategy(&self) -> Option<ColliderStrategy> {
+        collider_strategy(self.collider.as_deref().unwrap_or("trimesh"))
+    }
+}
+
+/// The [`ColliderStrategy`] a `collider` of [`NodeExtras`] or
+/// [`ColliderOverride`] names, if any.
+pub fn collider_strategy(name: &str) -> Option<ColliderStrategy> {
+    match name {
+        "trimesh" => Some(ColliderStrategy::Trimesh),
+        "convex" => Some(ColliderStrategy::ConvexHull),
+        "decomposition" => Some(ColliderStrategy::ConvexDecomposition(default())),
+        "sphere" => Some(ColliderStrategy::Sphere),
+        "capsule" => Some(ColliderStrategy::Capsule),
+        "cuboid" => Some(ColliderStrategy::Cuboid),
+        _ => None,
+    }
+}
+
+/// Collider settings replacing the `collider` and `layer` of every node whose
+/// [`NodeExtras`] give it a collider, so one scene can be reused on another
+/// layer or with a cheaper shape.
+#[derive(Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(default)]
+pub struct ColliderOverride {
+    pub collider: Option<String>,
+    pub layer: Option<String>,
+}
+
+/// Scene hook configuring each node from its [`NodeExtras`].
+pub fn configure_from_extras(entity: &EntityRef, cmds: &mut EntityCommands) {
+    configure_node(entity, cmds, &ColliderOverride::default());
+}
+
+/// Like [`configure_from_extras`], with the colliders changed by `overrides`.
+pub fn configure_with_override(
+    overrides: ColliderOverride,
+) -> impl Fn(&EntityRef, &mut EntityCommands) + Send + Sync + 'static {
+    move |entity, cmds| configure_node(entity, cmds, &overrides)
+}
+
+fn configure_node(entity: &EntityRef, cmds: &mut EntityCommands, overrides: &ColliderOverride) {
+    let Some(extras) = entity.get::<GltfExtras>() else {
+        return;
+    };
+    let name = entity.get::<Name>();
+    let mut extras = match NodeExtras::parse(extras) {
+        Ok(extras) => extras,
+        Err(err) => {
+            warn!("ignoring extras of {name:?}: {err}");
+            return;
+        }
+    };
+    if extras.layer.is_some() {
+        extras.collider = overrides.collider.clone().or(extras.collider);
+        extras.layer = overrides.layer.clone().or(extras.layer);
+    }
+    if extras.hidden {
+        cmds.insert(Visibility::Hidden);
+    }
+    if let Some(pieces) = extras.fracture {
+        cmds.insert(Fracturable { pieces });
+    }
+    if let Some(density) = extras.density {
+        cmds.insert((ColliderDensity(density), BaseDensity(density)));
+    }
+    if let Some(restitution) = extras.restitution {
+        cmds.insert(Restitution::new(restitution));
+    }
+    if let Some(friction) = extras.friction {
+        cmds.insert(Friction::new(friction));
+    }
+    let Some(layer) = extras.layer.clone() else {
+        return;
+    };
+    match extras.strategy() {
+        Some(strategy) => {
+            cmds.add(Collidable { layer, strategy });
+        }
+        None => warn!("unknown collider {:?} on {name:?}", extras.collider),
+    }
+}
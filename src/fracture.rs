@@ -0,0 +1,222 @@
+use bevy::prelude::*;
+use bevy::render::mesh::Indices;
+use bevy::render::mesh::PrimitiveTopology;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy_xpbd_3d::parry::transformation::try_convex_hull;
+use bevy_xpbd_3d::prelude::*;
+use rand::Rng;
+
+use crate::collidable::Collidable;
+use crate::collidable::ColliderStrategy;
+use crate::despawn::DelayedDespawn;
+use crate::health::apply_damage;
+use crate::health::despawn_destroyed;
+use crate::health::Destroyed;
+use crate::session::StateScoped;
+use crate::State;
+
+/// Breaks the meshes of [`Fracturable`] nodes into dynamic debris when their
+/// body is [`Destroyed`].
+#[derive(Clone, Debug)]
+pub struct FracturePlugin {
+    /// Collision layer of the debris.
+    pub debris_layer: String,
+    /// Seconds before debris is despawned.
+    pub lifetime: f32,
+    /// Speed at which debris flies apart, on top of the destroying impulse.
+    pub scatter: f32,
+}
+
+impl Default for FracturePlugin {
+    fn default() -> Self {
+        Self {
+            debris_layer: "Debris".into(),
+            lifetime: 5.0,
+            scatter: 2.0,
+        }
+    }
+}
+
+impl Plugin for FracturePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(FractureSettings {
+            debris_layer: self.debris_layer.clone(),
+            lifetime: self.lifetime,
+            scatter: self.scatter,
+        })
+        .add_systems(
+            Update,
+            fracture_destroyed
+                .after(apply_damage)
+                .before(despawn_destroyed)
+                .run_if(in_state(State::Play)),
+        );
+    }
+}
+
+#[derive(Resource, Clone, Debug)]
+pub struct FractureSettings {
+    pub debris_layer: String,
+    pub lifetime: f32,
+    pub scatter: f32,
+}
+
+/// Marks a scene node whose meshes break into `pieces` of debris.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct Fracturable {
+    pub pieces: usize,
+}
+
+/// Marks pieces of a fractured mesh.
+#[derive(Component, Debug)]
+pub struct Debris;
+
+/// A piece of a fractured mesh. Its vertices are relative to `center`.
+#[derive(Debug)]
+pub struct Fragment {
+    pub center: Vec3,
+    pub mesh: Mesh,
+}
+
+/// Splits `points` into at most `pieces` convex fragments.
+///
+/// Points are grouped by the nearest of `pieces` random seeds between the
+/// points' center and their surface, Voronoi style. Every fragment is the
+/// convex hull of its group and the center, so pieces meet in the middle.
+pub fn fracture(points: &[Vec3], pieces: usize, rng: &mut impl Rng) -> Vec<Fragment> {
+    if points.is_empty() || pieces == 0 {
+        return Vec::new();
+    }
+    let center = points.iter().sum::<Vec3>() / points.len() as f32;
+    let seeds: Vec<Vec3> = (0..pieces)
+        .map(|_| {
+            let surface = points[rng.gen_range(0..points.len())];
+            center.lerp(surface, rng.gen_range(0.3..0.8))
+        })
+        .collect();
+
+    let mut cells = vec![vec![center]; pieces];
+    for &point in points {
+        let nearest = (0..pieces)
+            .min_by(|&a, &b| {
+                let (da, db) = (
+                    seeds[a].distance_squared(point),
+                    seeds[b].distance_squared(point),
+                );
+                da.total_cmp(&db)
+            })
+            .unwrap();
+        cells[nearest].push(point);
+    }
+    cells.iter().filter_map(|cell| fragment(cell)).collect()
+}
+
+/// The convex hull of `points` as a flat shaded mesh, or `None` if they don't
+/// span a volume.
+fn fragment(points: &[Vec3]) -> Option<Fragment> {
+    if points.len() < 4 {
+        return None;
+    }
+    let (vertices, triangles) =
+        try_convex_hull(&points.iter().map(|&p| p.into()).collect::<Vec<_>>()).ok()?;
+    if triangles.len() < 4 {
+        return None;
+    }
+    let vertices: Vec<Vec3> = vertices.into_iter().map(Vec3::from).collect();
+    let center = vertices.iter().sum::<Vec3>() / vertices.len() as f32;
+
+    let mut positions = Vec::with_capacity(triangles.len() * 3);
+    let mut normals = Vec::with_capacity(triangles.len() * 3);
+    for [a, b, c] in triangles {
+        let [a, b, c] = [a, b, c].map(|i| vertices[i as usize] - center);
+        let normal = (b - a).cross(c - a).normalize_or_zero();
+        positions.extend([a, b, c]);
+        normals.extend([normal; 3]);
+    }
+    let indices = Indices::U32((0..positions.len() as u32).collect());
+    let mesh = Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+    .with_inserted_indices(indices);
+    Some(Fragment { center, mesh })
+}
+
+type MeshParts<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Handle<Mesh>,
+        &'static GlobalTransform,
+        Option<&'static Handle<StandardMaterial>>,
+    ),
+>;
+
+fn fracture_destroyed(
+    mut destroyed: EventReader<Destroyed>,
+    children: Query<&Children>,
+    nodes: Query<&Fracturable>,
+    parts: MeshParts,
+    settings: Res<FractureSettings>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut commands: Commands,
+) {
+    let mut rng = rand::thread_rng();
+    for event in destroyed.read() {
+        let mut debris = Vec::new();
+        for node in children.iter_descendants(event.entity) {
+            let Ok(&Fracturable { pieces }) = nodes.get(node) else {
+                continue;
+            };
+            for part in std::iter::once(node).chain(children.iter_descendants(node)) {
+                let Ok((mesh, transform, material)) = parts.get(part) else {
+                    continue;
+                };
+                let Some(positions) = meshes
+                    .get(mesh)
+                    .and_then(|m| m.attribute(Mesh::ATTRIBUTE_POSITION))
+                    .and_then(|p| p.as_float3())
+                else {
+                    continue;
+                };
+                let points: Vec<Vec3> = positions
+                    .iter()
+                    .map(|&p| transform.transform_point(p.into()))
+                    .collect();
+                let origin = transform.translation();
+                for fragment in fracture(&points, pieces, &mut rng) {
+                    let outward = (fragment.center - origin).normalize_or_zero();
+                    debris.push((fragment, outward, material.cloned().unwrap_or_default()));
+                }
+            }
+        }
+
+        let share = event.impulse / debris.len().max(1) as f32;
+        for (fragment, outward, material) in debris {
+            commands
+                .spawn((
+                    Name::new("debris"),
+                    Debris,
+                    StateScoped(State::Play),
+                    SpatialBundle::from_transform(Transform::from_translation(fragment.center)),
+                    RigidBody::Dynamic,
+                    LinearVelocity(outward * settings.scatter),
+                    ExternalImpulse::new(share),
+                    DelayedDespawn::after(settings.lifetime),
+                ))
+                .with_children(|c| {
+                    c.spawn(PbrBundle {
+                        mesh: meshes.add(fragment.mesh),
+                        material,
+                        ..default()
+                    });
+                })
+                .add(Collidable {
+                    layer: settings.debris_layer.clone(),
+                    strategy: ColliderStrategy::ConvexHull,
+                });
+        }
+    }
+}
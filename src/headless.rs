@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use bevy::app::PluginGroupBuilder;
+use bevy::app::ScheduleRunnerPlugin;
+use bevy::core::FrameCountPlugin;
+use bevy::core::TaskPoolPlugin;
+use bevy::core::TypeRegistrationPlugin;
+use bevy::gltf::GltfPlugin;
+use bevy::prelude::*;
+use bevy::render::mesh::MeshPlugin;
+use bevy::render::primitives::Aabb;
+use bevy::render::texture::ImagePlugin;
+use bevy::scene::ScenePlugin;
+use bevy::time::TimePlugin;
+use bevy::time::TimeUpdateStrategy;
+
+/// Length of one headless update.
+pub const HEADLESS_TIMESTEP: f64 = 1.0 / 60.0;
+
+/// [`MinimalPlugins`] plus what is needed to load and spawn the glTF scenes,
+/// without a window or GPU.
+///
+/// Every update advances time by a fixed [`HEADLESS_TIMESTEP`] and the runner
+/// loops without waiting, so runs are reproducible and finish as fast as the
+/// machine allows.
+pub struct HeadlessPlugins;
+
+impl PluginGroup for HeadlessPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add(TaskPoolPlugin::default())
+            .add(TypeRegistrationPlugin)
+            .add(FrameCountPlugin)
+            .add(TimePlugin)
+            .add(ScheduleRunnerPlugin::run_loop(Duration::ZERO))
+            .add(AssetPlugin {
+                watch_for_changes_override: Some(false),
+                ..default()
+            })
+            .add(TransformPlugin)
+            .add(HierarchyPlugin)
+            .add(MeshPlugin)
+            .add(ImagePlugin::default())
+            .add(ScenePlugin)
+            .add(GltfPlugin::default())
+            .add(HeadlessRenderTypesPlugin)
+    }
+}
+
+/// Registers the types normally provided by the render and PBR plugins, which
+/// the glTF loader and the scene spawner still need without a renderer.
+struct HeadlessRenderTypesPlugin;
+
+impl Plugin for HeadlessRenderTypesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<StandardMaterial>()
+            .register_asset_reflect::<StandardMaterial>()
+            .register_type::<Visibility>()
+            .register_type::<InheritedVisibility>()
+            .register_type::<ViewVisibility>()
+            .register_type::<Aabb>()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+                HEADLESS_TIMESTEP,
+            )));
+    }
+}
@@ -0,0 +1,119 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::impact::collide;
+use crate::impact::ImpactEvent;
+use crate::impact::ImpactPhase;
+use crate::State;
+
+/// Lets projectiles with [`Damage`] wear down targets with [`Health`], and
+/// despawns targets once they are [`Destroyed`].
+pub struct HealthPlugin;
+
+impl Plugin for HealthPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Health>()
+            .register_type::<Damage>()
+            .register_type::<Armor>()
+            .add_event::<Destroyed>()
+            .add_systems(
+                Update,
+                (apply_damage.after(collide), despawn_destroyed)
+                    .chain()
+                    .run_if(in_state(State::Play)),
+            );
+    }
+}
+
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Component)]
+pub struct Health {
+    pub current: f32,
+    pub max: f32,
+}
+
+impl Health {
+    pub fn new(max: f32) -> Self {
+        Self { current: max, max }
+    }
+}
+
+/// Damage a projectile deals per joule of kinetic energy at impact.
+#[derive(Component, Reflect, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[reflect(Component)]
+pub struct Damage(pub f32);
+
+impl Default for Damage {
+    fn default() -> Self {
+        Self(0.001)
+    }
+}
+
+/// Damage absorbed from every hit.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[reflect(Component)]
+pub struct Armor(pub f32);
+
+/// Health of `entity` ran out after being hit by `by`.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub struct Destroyed {
+    pub entity: Entity,
+    pub by: Entity,
+    /// Where the last hit landed.
+    pub point: Vec3,
+    /// Impulse of the last hit, along the projectile's relative velocity.
+    pub impulse: Vec3,
+}
+
+/// Damage of a hit by a projectile of `mass` with `relative_velocity` to its
+/// target, from the projectile's kinetic energy.
+pub fn impact_damage(mass: f32, relative_velocity: Vec3, damage: Damage, armor: Armor) -> f32 {
+    let energy = 0.5 * mass * relative_velocity.length_squared();
+    (energy * damage.0 - armor.0).max(0.0)
+}
+
+pub fn apply_damage(
+    mut impacts: EventReader<ImpactEvent>,
+    mut targets: Query<(&mut Health, Option<&Armor>)>,
+    mut destroyed: EventWriter<Destroyed>,
+) {
+    for impact in impacts.read() {
+        if impact.phase != ImpactPhase::Started {
+            continue;
+        }
+        let Some(damage) = impact.damage else {
+            continue;
+        };
+        let Ok((mut health, armor)) = targets.get_mut(impact.target) else {
+            continue;
+        };
+        // Already destroyed by an earlier hit this frame.
+        if health.current <= 0.0 {
+            continue;
+        }
+        let armor = armor.copied().unwrap_or_default();
+        health.current -= impact_damage(
+            impact.projectile_mass,
+            impact.relative_velocity,
+            damage,
+            armor,
+        );
+        if health.current <= 0.0 {
+            info!("{:?} destroyed by {:?}", impact.target, impact.projectile);
+            destroyed.send(Destroyed {
+                entity: impact.target,
+                by: impact.projectile,
+                point: impact.point,
+                impulse: impact.relative_velocity.normalize_or_zero() * impact.impulse_estimate,
+            });
+        }
+    }
+}
+
+pub fn despawn_destroyed(mut destroyed: EventReader<Destroyed>, mut commands: Commands) {
+    for Destroyed { entity, .. } in destroyed.read() {
+        if let Some(entity) = commands.get_entity(*entity) {
+            entity.despawn_recursive();
+        }
+    }
+}
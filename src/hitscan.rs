@@ -0,0 +1,217 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+use serde::Deserialize;
+
+use crate::despawn::DelayedDespawn;
+use crate::impact::collide;
+use crate::impact::ImpactEvent;
+use crate::impact::ImpactPhase;
+use crate::layers::CollisionLayerName;
+use crate::layers::CollisionMatrix;
+use crate::projectile::spread;
+use crate::projectile::FireAt;
+use crate::projectile::Launcher;
+use crate::session::StateScoped;
+use crate::State;
+use crate::Stats;
+
+/// Fires [`Hitscan`] launchers while in [`State::Play`], reporting hits as
+/// [`ImpactEvent`]s and leaving a [`Tracer`] along every ray.
+pub struct HitscanPlugin;
+
+impl Plugin for HitscanPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Hitscan>().add_systems(
+            Update,
+            fire_hitscan.before(collide).run_if(in_state(State::Play)),
+        );
+    }
+}
+
+/// Makes a [`Launcher`] cast rays that hit instantly instead of firing
+/// projectiles.
+///
+/// Hits are reported as if the launcher were a projectile of unit mass
+/// moving at the launcher's speed, so they place the same impact markers and
+/// deal the launcher's damage.
+#[derive(Component, Reflect, Deserialize, Clone, Debug)]
+#[reflect(Component)]
+#[serde(default)]
+pub struct Hitscan {
+    /// How far rays reach.
+    pub range: f32,
+    /// Layer of the rays. Its pairs in the [`CollisionMatrix`] decide what
+    /// they hit.
+    pub layer: String,
+    /// Seconds a [`Tracer`] stays.
+    pub tracer_lifetime: f32,
+}
+
+impl Default for Hitscan {
+    fn default() -> Self {
+        Self {
+            range: 100.0,
+            layer: "Ammo".into(),
+            tracer_lifetime: 0.1,
+        }
+    }
+}
+
+/// The path of a [`Hitscan`] ray, from the muzzle to where it hit or ran
+/// out of range.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct Tracer {
+    pub start: Vec3,
+    pub end: Vec3,
+}
+
+type HitscanLaunchers<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut Launcher,
+        &'static Hitscan,
+        &'static GlobalTransform,
+    ),
+>;
+
+/// Casts [`Hitscan`] rays and looks up what they hit.
+#[derive(SystemParam)]
+struct Rays<'w, 's> {
+    spatial: SpatialQuery<'w, 's>,
+    matrix: Res<'w, CollisionMatrix>,
+    colliders: Query<'w, 's, (&'static ColliderParent, Option<&'static CollisionLayerName>)>,
+    velocities: Query<'w, 's, &'static LinearVelocity>,
+}
+
+struct RayHit {
+    body: Entity,
+    velocity: Vec3,
+    /// Layer of the collider that was hit.
+    layer: Option<String>,
+    distance: f32,
+    normal: Vec3,
+}
+
+impl Rays<'_, '_> {
+    fn cast(&self, hitscan: &Hitscan, origin: Vec3, direction: Direction3d) -> Option<RayHit> {
+        let Some(layers) = self.matrix.layers(&hitscan.layer) else {
+            warn!("hitscan on unknown layer {:?}", hitscan.layer);
+            return None;
+        };
+        let filter = SpatialQueryFilter::from_mask(layers.filters);
+        let hit = self
+            .spatial
+            .cast_ray(origin, direction, hitscan.range, true, filter)?;
+        let (body, layer) = match self.colliders.get(hit.entity) {
+            Ok((parent, layer)) => (parent.get(), layer.map(|l| l.0.clone())),
+            Err(_) => (hit.entity, None),
+        };
+        Some(RayHit {
+            body,
+            velocity: self.velocities.get(body).map_or(Vec3::ZERO, |v| v.0),
+            layer,
+            distance: hit.time_of_impact,
+            normal: hit.normal,
+        })
+    }
+}
+
+fn fire_hitscan(
+    time: Res<Time>,
+    mut launchers: HitscanLaunchers,
+    mut requests: EventReader<FireAt>,
+    rays: Rays,
+    mut impacts: EventWriter<ImpactEvent>,
+    mut stats: ResMut<Stats>,
+    mut commands: Commands,
+) {
+    let mut rng = rand::thread_rng();
+    let requests: Vec<FireAt> = requests.read().copied().collect();
+    for (entity, mut launcher, hitscan, transform) in &mut launchers {
+        let targets = FireAt::targets(&requests, entity);
+        let bursts = launcher.trigger(time.delta(), transform, targets);
+        if bursts.is_empty() {
+            continue;
+        }
+        let muzzle = transform.transform_point(launcher.muzzle_offset);
+        let shots = bursts
+            .into_iter()
+            .flat_map(|burst| std::iter::repeat_n(burst, launcher.burst as usize));
+
+        for burst in shots {
+            let velocity = spread(burst, launcher.spread, &mut rng);
+            let Ok(direction) = Direction3d::new(velocity) else {
+                continue;
+            };
+            stats.shots_fired += 1;
+            let hit = rays.cast(hitscan, muzzle, direction);
+            let end = muzzle + *direction * hit.as_ref().map_or(hitscan.range, |hit| hit.distance);
+            commands.spawn((
+                Name::new("tracer"),
+                Tracer { start: muzzle, end },
+                DelayedDespawn::after(hitscan.tracer_lifetime),
+                StateScoped(State::Play),
+            ));
+
+            let Some(hit) = hit else {
+                continue;
+            };
+            let relative_velocity = velocity - hit.velocity;
+            info!("hitscan impact at {end:?}");
+            stats.collisions += 1;
+            impacts.send(ImpactEvent {
+                phase: ImpactPhase::Started,
+                projectile: entity,
+                target: hit.body,
+                point: end,
+                projectile_point: end,
+                normal: hit.normal,
+                relative_velocity,
+                projectile_mass: 1.0,
+                damage: Some(launcher.damage),
+                impulse_estimate: (-relative_velocity.dot(hit.normal)).max(0.0),
+                layer_pair: (Some(hitscan.layer.clone()), hit.layer),
+            });
+        }
+    }
+}
+
+/// Draws [`Tracer`]s as lines fading out until they are despawned.
+///
+/// Needs the gizmos of a rendering app, so unlike [`HitscanPlugin`] it isn't
+/// part of [`ShootingRangePlugin`](crate::ShootingRangePlugin).
+pub struct TracerPlugin {
+    pub color: Color,
+}
+
+impl Default for TracerPlugin {
+    fn default() -> Self {
+        Self {
+            color: Color::ORANGE,
+        }
+    }
+}
+
+impl Plugin for TracerPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(TracerColor(self.color))
+            .add_systems(Update, draw_tracers);
+    }
+}
+
+#[derive(Resource)]
+struct TracerColor(Color);
+
+fn draw_tracers(
+    tracers: Query<(&Tracer, &DelayedDespawn)>,
+    color: Res<TracerColor>,
+    mut gizmos: Gizmos,
+) {
+    for (tracer, despawn) in &tracers {
+        let color = color.0.with_a(color.0.a() * despawn.fraction_remaining());
+        gizmos.line(tracer.start, tracer.end, color);
+    }
+}
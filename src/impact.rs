@@ -0,0 +1,249 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_xpbd_3d::prelude::*;
+
+use crate::contact::ContactSummary;
+use crate::health::Damage;
+use crate::layers::CollisionLayerName;
+use crate::projectile::Projectile;
+use crate::session::START_RUN;
+use crate::State;
+use crate::Stats;
+
+/// Turns collisions into [`ImpactEvent`]s while in [`State::Play`].
+///
+/// Impacts are always reported when they start; `sustained` and `ended` opt
+/// into the other [`ImpactPhase`]s.
+#[derive(Clone, Debug, Default)]
+pub struct ImpactPlugin {
+    pub sustained: bool,
+    pub ended: bool,
+}
+
+impl Plugin for ImpactPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ImpactEvent>()
+            .insert_resource(ImpactSettings {
+                sustained: self.sustained,
+                ended: self.ended,
+            })
+            .init_resource::<LastImpacts>()
+            .add_systems(START_RUN, clear_last_impacts)
+            .add_systems(
+                Update,
+                (collide, separate).chain().run_if(in_state(State::Play)),
+            );
+    }
+}
+
+#[derive(Resource, Clone, Debug)]
+pub struct ImpactSettings {
+    pub sustained: bool,
+    pub ended: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ImpactPhase {
+    /// The colliders started touching this frame.
+    Started,
+    /// The colliders were already touching last frame.
+    Sustained,
+    /// The colliders stopped touching. The contact data is from the last
+    /// frame they touched, the velocity from this one.
+    Ended,
+}
+
+/// Two colliders hitting each other, seen from the one doing the hitting.
+///
+/// The `projectile` is whichever rigid body is a [`Projectile`], or the faster
+/// one if that doesn't decide it. Positions and directions are in world space.
+#[derive(Event, Clone, Debug, PartialEq)]
+pub struct ImpactEvent {
+    pub phase: ImpactPhase,
+    /// Rigid body of the projectile, or the launcher of a
+    /// [`Hitscan`](crate::hitscan::Hitscan) ray.
+    pub projectile: Entity,
+    /// Rigid body that was hit.
+    pub target: Entity,
+    /// Impact point on the target's surface.
+    pub point: Vec3,
+    /// Impact point on the projectile's surface.
+    pub projectile_point: Vec3,
+    /// Surface normal of the target, pointing towards the projectile.
+    pub normal: Vec3,
+    /// Velocity of the projectile relative to the target.
+    pub relative_velocity: Vec3,
+    /// Mass of the projectile, 1 if it has none, like a hitscan ray.
+    pub projectile_mass: f32,
+    /// [`Damage`] of the projectile, or of the launcher of a hitscan ray.
+    pub damage: Option<Damage>,
+    /// Impulse the solver applied or, between bodies it doesn't push apart
+    /// like kinematic ones, the impulse that would stop the projectile.
+    pub impulse_estimate: f32,
+    /// [`CollisionLayerName`]s of the projectile and the target.
+    pub layer_pair: (Option<String>, Option<String>),
+}
+
+/// Latest impact per collider pair, reported again when they separate.
+/// Cleared when a run starts, as pairs still touching when the last one ended
+/// are never seen separating.
+#[derive(Resource, Default)]
+pub struct LastImpacts(HashMap<(Entity, Entity), ImpactEvent>);
+
+type Colliders<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static ColliderParent,
+        &'static GlobalTransform,
+        Option<&'static CollisionLayerName>,
+    ),
+    With<Collider>,
+>;
+
+type Bodies<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Option<&'static LinearVelocity>,
+        Option<&'static Mass>,
+        Option<&'static Damage>,
+        Has<Projectile>,
+    ),
+>;
+
+fn velocity(bodies: &Bodies, body: Entity) -> Vec3 {
+    match bodies.get(body) {
+        Ok((Some(velocity), ..)) => velocity.0,
+        _ => Vec3::ZERO,
+    }
+}
+
+fn mass(bodies: &Bodies, body: Entity) -> f32 {
+    match bodies.get(body) {
+        Ok((_, Some(mass), ..)) if mass.0.is_finite() && mass.0 > 0.0 => mass.0,
+        _ => 1.0,
+    }
+}
+
+fn damage(bodies: &Bodies, body: Entity) -> Option<Damage> {
+    bodies
+        .get(body)
+        .ok()
+        .and_then(|(_, _, damage, _)| damage.copied())
+}
+
+pub fn collide(
+    colliders: Colliders,
+    bodies: Bodies,
+    mut collisions: EventReader<Collision>,
+    settings: Res<ImpactSettings>,
+    mut last: ResMut<LastImpacts>,
+    mut impacts: EventWriter<ImpactEvent>,
+    mut stats: ResMut<Stats>,
+) {
+    for Collision(contacts) in collisions.read() {
+        let phase = match contacts.during_previous_frame {
+            false => ImpactPhase::Started,
+            true => ImpactPhase::Sustained,
+        };
+        // Sustained contacts are still tracked for `ended`, as the contact
+        // data is gone by the time the colliders separate.
+        if phase == ImpactPhase::Sustained && !settings.sustained && !settings.ended {
+            continue;
+        }
+        let Ok([(parent1, transform1, layer1), (parent2, transform2, layer2)]) =
+            colliders.get_many([contacts.entity1, contacts.entity2])
+        else {
+            continue;
+        };
+        let Some(summary) = ContactSummary::new(contacts) else {
+            continue;
+        };
+        let (point1, point2) = summary.world_points(transform1, transform2);
+        let normal1 = transform1.compute_transform().rotation * summary.normal1;
+        let normal2 = transform2.compute_transform().rotation * summary.normal2;
+        let (body1, body2) = (parent1.get(), parent2.get());
+        let (velocity1, velocity2) = (velocity(&bodies, body1), velocity(&bodies, body2));
+
+        let is_projectile = |body| bodies.get(body).is_ok_and(|(.., p)| p);
+        let first_hits = match (is_projectile(body1), is_projectile(body2)) {
+            (true, false) => true,
+            (false, true) => false,
+            _ => velocity1.length_squared() >= velocity2.length_squared(),
+        };
+        let name = |layer: Option<&CollisionLayerName>| layer.map(|l| l.0.clone());
+        let mut impact = if first_hits {
+            ImpactEvent {
+                phase,
+                projectile: body1,
+                target: body2,
+                point: point2,
+                projectile_point: point1,
+                normal: normal2,
+                relative_velocity: velocity1 - velocity2,
+                projectile_mass: mass(&bodies, body1),
+                damage: damage(&bodies, body1),
+                impulse_estimate: contacts.total_normal_impulse,
+                layer_pair: (name(layer1), name(layer2)),
+            }
+        } else {
+            ImpactEvent {
+                phase,
+                projectile: body2,
+                target: body1,
+                point: point1,
+                projectile_point: point2,
+                normal: normal1,
+                relative_velocity: velocity2 - velocity1,
+                projectile_mass: mass(&bodies, body2),
+                damage: damage(&bodies, body2),
+                impulse_estimate: contacts.total_normal_impulse,
+                layer_pair: (name(layer2), name(layer1)),
+            }
+        };
+        if impact.impulse_estimate <= 0.0 {
+            let closing_speed = -impact.relative_velocity.dot(impact.normal);
+            impact.impulse_estimate = closing_speed.max(0.0) * impact.projectile_mass;
+        }
+
+        if settings.ended {
+            last.0
+                .insert((contacts.entity1, contacts.entity2), impact.clone());
+        }
+        if phase == ImpactPhase::Started {
+            info!("impact at {:?}", impact.point);
+            stats.collisions += 1;
+        }
+        if phase == ImpactPhase::Started || settings.sustained {
+            impacts.send(impact);
+        }
+    }
+}
+
+fn clear_last_impacts(mut last: ResMut<LastImpacts>) {
+    last.0.clear();
+}
+
+fn separate(
+    bodies: Bodies,
+    mut ended: EventReader<CollisionEnded>,
+    mut last: ResMut<LastImpacts>,
+    mut impacts: EventWriter<ImpactEvent>,
+) {
+    for CollisionEnded(entity1, entity2) in ended.read() {
+        let impact = last
+            .0
+            .remove(&(*entity1, *entity2))
+            .or_else(|| last.0.remove(&(*entity2, *entity1)));
+        let Some(impact) = impact else {
+            continue;
+        };
+        impacts.send(ImpactEvent {
+            phase: ImpactPhase::Ended,
+            relative_velocity: velocity(&bodies, impact.projectile)
+                - velocity(&bodies, impact.target),
+            ..impact
+        });
+    }
+}
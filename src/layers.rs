@@ -0,0 +1,194 @@
+use std::fmt;
+
+use bevy::asset::io::Reader;
+use bevy::asset::AssetLoader;
+use bevy::asset::AsyncReadExt;
+use bevy::asset::LoadContext;
+use bevy::prelude::*;
+use bevy::utils::BoxedFuture;
+use bevy_xpbd_3d::components::CollisionLayers;
+use bevy_xpbd_3d::components::LayerMask;
+use serde::Deserialize;
+
+/// Loads `.layers.ron` files as [`CollisionMatrix`] assets and keeps the
+/// [`CollisionMatrix`] resource and every [`CollisionLayerName`] in sync with
+/// the last one loaded or modified.
+pub struct CollisionMatrixPlugin;
+
+impl Plugin for CollisionMatrixPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<CollisionMatrix>()
+            .register_asset_loader(CollisionMatrixLoader)
+            .init_resource::<CollisionMatrix>()
+            .add_systems(
+                Update,
+                (
+                    sync_collision_matrix,
+                    apply_collision_matrix.run_if(resource_changed::<CollisionMatrix>),
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Asset path of the [`CollisionMatrix`] in
+/// [`BlenderAssets`](crate::BlenderAssets).
+pub const COLLISION_MATRIX: &str = "collision.layers.ron";
+
+/// Named collision layers and the pairs of layers that collide.
+///
+/// Pairs are symmetric. The default matrix only has `Ammo` and `Object`,
+/// colliding with each other.
+#[derive(Asset, Resource, TypePath, Deserialize, Clone, Debug, PartialEq)]
+pub struct CollisionMatrix {
+    /// Layer names, in bit order. At most 32.
+    pub layers: Vec<String>,
+    pub pairs: Vec<(String, String)>,
+}
+
+impl Default for CollisionMatrix {
+    fn default() -> Self {
+        Self {
+            layers: vec!["Ammo".into(), "Object".into()],
+            pairs: vec![("Ammo".into(), "Object".into())],
+        }
+    }
+}
+
+impl CollisionMatrix {
+    /// The [`CollisionLayers`] of an entity on the layer called `name`.
+    pub fn layers(&self, name: &str) -> Option<CollisionLayers> {
+        let membership = self.mask(name)?;
+        let filters = self
+            .pairs
+            .iter()
+            .filter_map(|(a, b)| match (a == name, b == name) {
+                (true, _) => self.mask(b),
+                (_, true) => self.mask(a),
+                _ => None,
+            })
+            .fold(LayerMask::NONE, |acc, mask| acc | mask);
+        Some(CollisionLayers::new(membership, filters))
+    }
+
+    /// The bit of the layer called `name`.
+    pub fn mask(&self, name: &str) -> Option<LayerMask> {
+        let index = self.layers.iter().position(|layer| layer == name)?;
+        Some(LayerMask(1 << index))
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.layers.len() > 32 {
+            return Err(format!("{} layers, at most 32 allowed", self.layers.len()));
+        }
+        for (a, b) in &self.pairs {
+            for name in [a, b] {
+                if self.mask(name).is_none() {
+                    return Err(format!("pair ({a:?}, {b:?}) names unknown layer {name:?}"));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The name of the layer an entity's [`CollisionLayers`] come from, so they
+/// can be recomputed when the [`CollisionMatrix`] changes.
+#[derive(Component, Clone, Debug, PartialEq, Eq, Deref)]
+pub struct CollisionLayerName(pub String);
+
+#[derive(Default)]
+struct CollisionMatrixLoader;
+
+impl AssetLoader for CollisionMatrixLoader {
+    type Asset = CollisionMatrix;
+    type Settings = ();
+    type Error = CollisionMatrixLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<CollisionMatrix, CollisionMatrixLoaderError>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let matrix: CollisionMatrix = ron::de::from_bytes(&bytes)?;
+            matrix
+                .validate()
+                .map_err(CollisionMatrixLoaderError::Invalid)?;
+            Ok(matrix)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["layers.ron"]
+    }
+}
+
+#[derive(Debug)]
+pub enum CollisionMatrixLoaderError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+    Invalid(String),
+}
+
+impl fmt::Display for CollisionMatrixLoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CollisionMatrixLoaderError::Io(err) => write!(f, "cannot read collision matrix: {err}"),
+            CollisionMatrixLoaderError::Ron(err) => write!(f, "invalid collision matrix: {err}"),
+            CollisionMatrixLoaderError::Invalid(reason) => {
+                write!(f, "invalid collision matrix: {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CollisionMatrixLoaderError {}
+
+impl From<std::io::Error> for CollisionMatrixLoaderError {
+    fn from(err: std::io::Error) -> Self {
+        CollisionMatrixLoaderError::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for CollisionMatrixLoaderError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        CollisionMatrixLoaderError::Ron(err)
+    }
+}
+
+fn sync_collision_matrix(
+    mut events: EventReader<AssetEvent<CollisionMatrix>>,
+    matrices: Res<Assets<CollisionMatrix>>,
+    mut matrix: ResMut<CollisionMatrix>,
+) {
+    for event in events.read() {
+        if let AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } = event {
+            if let Some(loaded) = matrices.get(*id) {
+                info!("collision matrix loaded");
+                *matrix = loaded.clone();
+            }
+        }
+    }
+}
+
+fn apply_collision_matrix(
+    matrix: Res<CollisionMatrix>,
+    entities: Query<(Entity, &CollisionLayerName)>,
+    mut commands: Commands,
+) {
+    for (entity, name) in &entities {
+        match matrix.layers(name) {
+            Some(layers) => {
+                commands.entity(entity).insert(layers);
+            }
+            None => warn!(
+                "{entity:?} is on layer {:?}, which no longer exists",
+                **name
+            ),
+        }
+    }
+}
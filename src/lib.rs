@@ -0,0 +1,200 @@
+use bevy::gltf::Gltf;
+use bevy::prelude::*;
+use bevy_asset_loader::asset_collection::AssetCollection;
+use bevy_asset_loader::loading_state::config::ConfigureLoadingState;
+use bevy_asset_loader::loading_state::LoadingState;
+use bevy_asset_loader::loading_state::LoadingStateAppExt;
+use bevy_scene_hook::HookPlugin;
+use bevy_xpbd_3d::resources::Gravity;
+
+pub mod aim;
+pub mod body;
+pub mod camera;
+pub mod ccd;
+pub mod collidable;
+pub mod contact;
+pub mod despawn;
+pub mod extras;
+pub mod fracture;
+pub mod headless;
+pub mod health;
+pub mod hitscan;
+pub mod impact;
+pub mod layers;
+pub mod loading;
+pub mod marker;
+pub mod math;
+pub mod menu;
+pub mod pool;
+pub mod projectile;
+pub mod scenario;
+pub mod session;
+pub mod tracker;
+
+use body::BodyPlugin;
+use ccd::CcdPlugin;
+use collidable::CollidablePlugin;
+use despawn::DespawnPlugin;
+use fracture::FracturePlugin;
+use health::HealthPlugin;
+use hitscan::HitscanPlugin;
+use impact::ImpactPlugin;
+use layers::CollisionMatrix;
+use layers::CollisionMatrixPlugin;
+use loading::track_collection;
+use loading::LoadingPlugin;
+use marker::ImpactMarkerPlugin;
+use projectile::ProjectilePlugin;
+use scenario::ScenarioAssets;
+use scenario::ScenarioPlugin;
+use session::SessionPlugin;
+use tracker::ContactTrackerPlugin;
+
+#[derive(Clone, Eq, PartialEq, Debug, Hash, Default, States)]
+pub enum State {
+    #[default]
+    Load,
+    /// When an asset couldn't be loaded, as told by the
+    /// [`LoadingProgress`](loading::LoadingProgress).
+    LoadFailed,
+    /// Between runs, picking the scenario.
+    Menu,
+    Play,
+    Paused,
+    /// After every target of the run has been destroyed.
+    Results,
+}
+
+impl State {
+    pub const ALL: [State; 6] = [
+        State::Load,
+        State::LoadFailed,
+        State::Menu,
+        State::Play,
+        State::Paused,
+        State::Results,
+    ];
+
+    /// Whether this state is `scope` or nested in it: pausing and showing the
+    /// results are still part of [`State::Play`].
+    pub fn is_within(&self, scope: &State) -> bool {
+        self == scope || (*scope == State::Play && matches!(self, State::Paused | State::Results))
+    }
+
+    /// Whether a run is going on, in play, paused or showing its results.
+    pub fn in_session(&self) -> bool {
+        self.is_within(&State::Play)
+    }
+}
+
+#[derive(AssetCollection, Resource)]
+pub struct BlenderAssets {
+    // Holding the files themselves keeps the loader from dropping the last
+    // handle to a file while its sub-assets are being inserted, which panics.
+    #[asset(path = "rock.glb")]
+    pub rock_gltf: Handle<Gltf>,
+
+    #[asset(path = "ammo.glb")]
+    pub ammo_gltf: Handle<Gltf>,
+
+    #[asset(path = "rock.glb#Mesh0/Primitive0")]
+    pub ball: Handle<Mesh>,
+
+    #[asset(path = "rock.glb#Mesh1/Primitive0")]
+    pub spike: Handle<Mesh>,
+
+    #[asset(path = "rock.glb#Scene0")]
+    pub rock: Handle<Scene>,
+
+    #[asset(path = "ammo.glb#Scene0")]
+    pub ammo: Handle<Scene>,
+
+    /// At [`COLLISION_MATRIX`](layers::COLLISION_MATRIX), which scenarios
+    /// are checked against.
+    #[asset(path = "collision.layers.ron")]
+    pub collision_matrix: Handle<CollisionMatrix>,
+}
+
+/// Loads [`BlenderAssets`] and builds the [`Scenario`](scenario::Scenario) for
+/// every run of the [session](SessionPlugin): targets, launchers firing at them
+/// and lights.
+///
+/// Physics is left to the app: add [`PhysicsPlugins`](bevy_xpbd_3d::plugins::PhysicsPlugins)
+/// before this plugin.
+#[derive(Clone, Debug)]
+pub struct ShootingRangePlugin {
+    pub scenario: ScenarioPlugin,
+    pub session: SessionPlugin,
+    pub fracture: FracturePlugin,
+    /// Gravity of the scene. It only pulls dynamic bodies, and there is none
+    /// by default.
+    pub gravity: Vec3,
+    pub projectile: ProjectilePlugin,
+    pub ccd: CcdPlugin,
+    pub impacts: ImpactPlugin,
+    pub impact_markers: ImpactMarkerPlugin,
+}
+
+impl Default for ShootingRangePlugin {
+    fn default() -> Self {
+        Self {
+            scenario: ScenarioPlugin::default(),
+            session: SessionPlugin::default(),
+            fracture: FracturePlugin::default(),
+            gravity: Vec3::ZERO,
+            projectile: ProjectilePlugin::default(),
+            ccd: CcdPlugin::default(),
+            impacts: ImpactPlugin::default(),
+            impact_markers: ImpactMarkerPlugin::default(),
+        }
+    }
+}
+
+impl Plugin for ShootingRangePlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<HookPlugin>() {
+            app.add_plugins(HookPlugin);
+        }
+        app.init_state::<State>()
+            .insert_resource(Gravity(self.gravity))
+            .init_resource::<Stats>()
+            .add_loading_state(
+                // The loading plugin moves on once every dependency is loaded.
+                LoadingState::new(State::Load)
+                    .on_failure_continue_to_state(State::LoadFailed)
+                    .load_collection::<BlenderAssets>()
+                    .load_collection::<ScenarioAssets>(),
+            )
+            .add_systems(
+                OnEnter(State::Load),
+                (
+                    track_collection::<BlenderAssets>,
+                    track_collection::<ScenarioAssets>,
+                ),
+            )
+            .add_plugins((
+                LoadingPlugin,
+                self.scenario.clone(),
+                self.session.clone(),
+                BodyPlugin,
+                CollidablePlugin,
+                CollisionMatrixPlugin,
+                DespawnPlugin,
+                HealthPlugin,
+                self.fracture.clone(),
+                self.projectile.clone(),
+                self.ccd.clone(),
+                HitscanPlugin,
+                self.impacts.clone(),
+                ContactTrackerPlugin,
+                self.impact_markers.clone(),
+            ));
+    }
+}
+
+/// Counters of what happened during a run.
+#[derive(Resource, Default, Debug)]
+pub struct Stats {
+    pub shots_fired: usize,
+    pub collisions: usize,
+}
@@ -0,0 +1,207 @@
+use bevy::asset::io::AssetSourceId;
+use bevy::asset::AssetPath;
+use bevy::asset::LoadState;
+use bevy::asset::RecursiveDependencyLoadState;
+use bevy::asset::UntypedAssetLoadFailedEvent;
+use bevy::prelude::*;
+use bevy_asset_loader::asset_collection::AssetCollection;
+
+use crate::State;
+
+/// Follows every asset of the collections [tracked](track_collection) while
+/// in [`State::Load`] in [`LoadingProgress`]. Moves on to [`State::Menu`] once
+/// they are all loaded along with their dependencies, which the loading state
+/// alone doesn't wait for, or to [`State::LoadFailed`] as soon as one of them
+/// can't load.
+///
+/// That includes sub-assets missing from their file, such as a mistyped
+/// `rock.glb#Mesh1/Primitive0`, which the asset server only logs and the
+/// loading state would wait for forever.
+pub struct LoadingPlugin;
+
+impl Plugin for LoadingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LoadingProgress>()
+            .add_systems(
+                Update,
+                (record_failures, update_progress, continue_when_loaded)
+                    .chain()
+                    .run_if(in_state(State::Load)),
+            )
+            .add_systems(OnEnter(State::LoadFailed), log_failures);
+    }
+}
+
+/// Every asset being loaded, in the order they were tracked, and why loading
+/// failed if it did.
+#[derive(Resource, Default, Debug)]
+pub struct LoadingProgress {
+    pub assets: Vec<TrackedAsset>,
+    /// Readable messages, one per failure.
+    pub errors: Vec<String>,
+    /// Whether each tracked collection has been inserted.
+    collections: Vec<fn(&World) -> bool>,
+}
+
+#[derive(Clone, Debug)]
+pub struct TrackedAsset {
+    /// Asset path, such as `rock.glb#Scene0`.
+    pub path: String,
+    pub status: LoadStatus,
+    handle: UntypedHandle,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LoadStatus {
+    Loading,
+    /// Along with its dependencies.
+    Loaded,
+    Failed,
+}
+
+impl LoadingProgress {
+    /// Follows `handle`, unless it already is.
+    pub fn track(&mut self, asset_server: &AssetServer, handle: UntypedHandle) {
+        if self.assets.iter().any(|asset| asset.handle == handle) {
+            return;
+        }
+        // Untyped handles, which asset collections use, load through a source
+        // of their own that isn't worth showing.
+        let path = asset_server.get_path(handle.id()).map_or_else(
+            || format!("{:?}", handle.id()),
+            |path| path.with_source(AssetSourceId::Default).to_string(),
+        );
+        self.assets.push(TrackedAsset {
+            path,
+            status: LoadStatus::Loading,
+            handle,
+        });
+    }
+
+    pub fn loaded(&self) -> usize {
+        self.assets
+            .iter()
+            .filter(|asset| asset.status == LoadStatus::Loaded)
+            .count()
+    }
+
+    pub fn failed(&self) -> bool {
+        !self.errors.is_empty()
+    }
+}
+
+/// Starts loading the assets of `A` and tracks them in [`LoadingProgress`].
+/// Run it on entering [`State::Load`] for each collection of the loading
+/// state, which reuses the same handles.
+pub fn track_collection<A: AssetCollection>(world: &mut World) {
+    let handles = A::load(world);
+    world.resource_scope(|world, mut progress: Mut<LoadingProgress>| {
+        let asset_server = world.resource::<AssetServer>();
+        for handle in handles {
+            progress.track(asset_server, handle);
+        }
+        progress
+            .collections
+            .push(|world| world.contains_resource::<A>());
+    });
+}
+
+fn record_failures(
+    mut failures: EventReader<UntypedAssetLoadFailedEvent>,
+    mut progress: ResMut<LoadingProgress>,
+) {
+    for failure in failures.read() {
+        progress.errors.push(failure.error.to_string());
+    }
+}
+
+fn update_progress(
+    asset_server: Res<AssetServer>,
+    mut progress: ResMut<LoadingProgress>,
+    mut next: ResMut<NextState<State>>,
+) {
+    let progress = &mut *progress;
+    let loaded_files: Vec<String> = progress
+        .assets
+        .iter()
+        .filter(|asset| asset.status == LoadStatus::Loaded)
+        .map(|asset| asset.path.clone())
+        .collect();
+    for asset in &mut progress.assets {
+        if asset.status != LoadStatus::Loading {
+            continue;
+        }
+        let id = asset.handle.id();
+        asset.status = match asset_server.get_recursive_dependency_load_state(id) {
+            Some(RecursiveDependencyLoadState::Loaded) => LoadStatus::Loaded,
+            Some(RecursiveDependencyLoadState::Failed) => LoadStatus::Failed,
+            _ => LoadStatus::Loading,
+        };
+        match asset.status {
+            LoadStatus::Failed => {
+                // Failures of its own are already reported with its path.
+                let reported = progress
+                    .errors
+                    .iter()
+                    .any(|error| error.contains(&asset.path));
+                if !reported {
+                    progress.errors.push(format!(
+                        "{} or one of its dependencies failed to load",
+                        asset.path
+                    ));
+                }
+            }
+            LoadStatus::Loading => {
+                if let Some(error) = missing_label(&asset_server, &asset.path, &loaded_files) {
+                    asset.status = LoadStatus::Failed;
+                    progress.errors.push(error);
+                }
+            }
+            LoadStatus::Loaded => {}
+        }
+    }
+    if progress.failed() {
+        next.set(State::LoadFailed);
+    }
+}
+
+/// Labeled assets are loaded along with their file, so one not loaded once its
+/// file is doesn't exist. `loaded_files` are the tracked paths seen loaded.
+fn missing_label(
+    asset_server: &AssetServer,
+    path: &str,
+    loaded_files: &[String],
+) -> Option<String> {
+    let path = AssetPath::parse(path);
+    let label = path.label()?;
+    let file = path.without_label();
+    let loaded = |path: &AssetPath| {
+        let ids = asset_server.get_path_ids(path);
+        let loaded = ids
+            .iter()
+            .any(|&id| asset_server.get_load_state(id) == Some(LoadState::Loaded));
+        (!ids.is_empty()).then_some(loaded)
+    };
+    // Asking for the missing label reloads the file, which the asset server
+    // then leaves loading for good, so a file seen loaded before counts too.
+    let file_loaded = loaded_files.contains(&file.to_string()) || loaded(&file)?;
+    // Collections track untyped wrappers, which finish loading a little after
+    // the asset they wrap, so this looks at the asset itself, once it exists.
+    (file_loaded && !loaded(&path)?).then(|| format!("{file} has no sub-asset named `{label}`"))
+}
+
+fn continue_when_loaded(world: &mut World) {
+    let progress = world.resource::<LoadingProgress>();
+    let loaded = !progress.failed()
+        && progress.loaded() == progress.assets.len()
+        && progress.collections.iter().all(|inserted| inserted(world));
+    if loaded {
+        world.resource_mut::<NextState<State>>().set(State::Menu);
+    }
+}
+
+fn log_failures(progress: Res<LoadingProgress>) {
+    for error in &progress.errors {
+        error!("loading failed: {error}");
+    }
+}
@@ -0,0 +1,267 @@
+use bevy::prelude::*;
+
+use crate::despawn::DelayedDespawn;
+use crate::impact::collide;
+use crate::impact::ImpactEvent;
+use crate::impact::ImpactPhase;
+use crate::impact::ImpactPlugin;
+use crate::pool::park;
+use crate::pool::unpark;
+use crate::pool::Pool;
+use crate::pool::PoolPlugin;
+use crate::pool::Pooled;
+use crate::session::StateScoped;
+use crate::session::START_RUN;
+use crate::tracker::track_contacts;
+use crate::tracker::ContactTracker;
+use crate::tracker::ContactTrackerPlugin;
+use crate::BlenderAssets;
+use crate::State;
+
+/// Spawns a glowing marker at both contact points whenever an
+/// [`ImpactEvent`] starts, and lights up [`ContactGlow`] entities while they
+/// are touched. Adds the [`ImpactPlugin`] and [`ContactTrackerPlugin`] if
+/// they are missing.
+#[derive(Clone, Debug)]
+pub struct ImpactMarkerPlugin {
+    pub color: Color,
+    /// Colors for hits between two layers, in either order, instead of `color`.
+    pub pair_colors: Vec<(String, String, Color)>,
+    /// Multiplier applied to the color for the emissive channel.
+    pub emissive_intensity: f32,
+    pub scale: f32,
+    /// Seconds before a marker is returned to its pool.
+    pub lifetime: f32,
+    /// Markers spawned up front. More are added when they are all in use.
+    pub pool_size: usize,
+}
+
+impl Default for ImpactMarkerPlugin {
+    fn default() -> Self {
+        Self {
+            color: Color::BLUE,
+            pair_colors: Vec::new(),
+            emissive_intensity: 500.0,
+            scale: 3.0,
+            lifetime: 1.0,
+            pool_size: 16,
+        }
+    }
+}
+
+impl Plugin for ImpactMarkerPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<ImpactPlugin>() {
+            app.add_plugins(ImpactPlugin::default());
+        }
+        if !app.is_plugin_added::<ContactTrackerPlugin>() {
+            app.add_plugins(ContactTrackerPlugin);
+        }
+        app.insert_resource(ImpactMarkerSettings {
+            color: self.color,
+            pair_colors: self.pair_colors.clone(),
+            emissive_intensity: self.emissive_intensity,
+            scale: self.scale,
+            lifetime: self.lifetime,
+            pool_size: self.pool_size,
+        })
+        .add_plugins(PoolPlugin::<ImpactMarker>::default())
+        .add_systems(START_RUN, (create_impact_fx, fill_marker_pool).chain())
+        .add_systems(
+            Update,
+            (
+                mark_impacts.after(collide),
+                glow_while_touched.after(track_contacts),
+            )
+                .run_if(in_state(State::Play)),
+        );
+    }
+}
+
+#[derive(Resource, Clone, Debug)]
+pub struct ImpactMarkerSettings {
+    pub color: Color,
+    pub pair_colors: Vec<(String, String, Color)>,
+    pub emissive_intensity: f32,
+    pub scale: f32,
+    pub lifetime: f32,
+    pub pool_size: usize,
+}
+
+/// Mesh and materials shared by all impact markers, created when the first
+/// run starts.
+#[derive(Resource, Debug)]
+pub struct ImpactFxAssets {
+    pub mesh: Handle<Mesh>,
+    /// For hits without a material of their own in `pairs`.
+    pub material: Handle<StandardMaterial>,
+    pub pairs: Vec<(String, String, Handle<StandardMaterial>)>,
+}
+
+impl ImpactFxAssets {
+    /// The material for a hit between colliders on the given layers.
+    pub fn material(
+        &self,
+        layer1: Option<&str>,
+        layer2: Option<&str>,
+    ) -> &Handle<StandardMaterial> {
+        let (Some(layer1), Some(layer2)) = (layer1, layer2) else {
+            return &self.material;
+        };
+        self.pairs
+            .iter()
+            .find(|(a, b, _)| {
+                let pair = (a.as_str(), b.as_str());
+                pair == (layer1, layer2) || pair == (layer2, layer1)
+            })
+            .map_or(&self.material, |(_, _, material)| material)
+    }
+}
+
+/// Marks the entities placed by [`mark_impacts`].
+#[derive(Component)]
+pub struct ImpactMarker;
+
+fn create_impact_fx(
+    fx: Option<Res<ImpactFxAssets>>,
+    settings: Res<ImpactMarkerSettings>,
+    assets: Res<BlenderAssets>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    if fx.is_some() {
+        return;
+    }
+    let mut glowing = |color: Color| {
+        materials.add(StandardMaterial {
+            base_color: color,
+            emissive: color * settings.emissive_intensity,
+            ..default()
+        })
+    };
+    commands.insert_resource(ImpactFxAssets {
+        mesh: assets.ball.clone(),
+        material: glowing(settings.color),
+        pairs: settings
+            .pair_colors
+            .iter()
+            .map(|(a, b, color)| (a.clone(), b.clone(), glowing(*color)))
+            .collect(),
+    });
+}
+
+fn marker(fx: &ImpactFxAssets) -> impl Bundle {
+    (
+        MaterialMeshBundle {
+            mesh: fx.mesh.clone(),
+            material: fx.material.clone(),
+            ..default()
+        },
+        ImpactMarker,
+        Pooled,
+        StateScoped(State::Play),
+    )
+}
+
+fn fill_marker_pool(
+    settings: Res<ImpactMarkerSettings>,
+    fx: Res<ImpactFxAssets>,
+    mut commands: Commands,
+) {
+    for _ in 0..settings.pool_size {
+        commands.spawn(marker(&fx)).add(park);
+    }
+}
+
+/// Places a marker at both contact points of every impact that starts.
+pub fn mark_impacts(
+    mut impacts: EventReader<ImpactEvent>,
+    settings: Res<ImpactMarkerSettings>,
+    fx: Res<ImpactFxAssets>,
+    mut pool: ResMut<Pool<ImpactMarker>>,
+    mut commands: Commands,
+) {
+    for impact in impacts.read() {
+        if impact.phase != ImpactPhase::Started {
+            continue;
+        }
+        let (layer1, layer2) = &impact.layer_pair;
+        let material = fx.material(layer1.as_deref(), layer2.as_deref());
+        for p in [impact.projectile_point, impact.point] {
+            let mut entity = match pool.acquire() {
+                Some(parked) => {
+                    let mut entity = commands.entity(parked);
+                    entity.add(unpark);
+                    entity
+                }
+                None => commands.spawn(marker(&fx)),
+            };
+            entity.insert((
+                material.clone(),
+                Transform::from_translation(p).with_scale(Vec3::splat(settings.scale)),
+                DelayedDespawn::after(settings.lifetime),
+            ));
+        }
+    }
+}
+
+/// Lights up its entity while any other rigid body touches it, e.g. while
+/// ammo passes through the rock.
+#[derive(Component, Clone, Debug)]
+pub struct ContactGlow {
+    pub color: Color,
+    /// Light intensity in lumens.
+    pub intensity: f32,
+    light: Option<Entity>,
+}
+
+impl ContactGlow {
+    pub fn new(color: Color, intensity: f32) -> Self {
+        Self {
+            color,
+            intensity,
+            light: None,
+        }
+    }
+
+    /// The light currently shining, if the entity is touched.
+    pub fn light(&self) -> Option<Entity> {
+        self.light
+    }
+}
+
+impl Default for ContactGlow {
+    fn default() -> Self {
+        Self::new(Color::BLUE, 1_000_000.0)
+    }
+}
+
+fn glow_while_touched(
+    tracker: Res<ContactTracker>,
+    mut glows: Query<(Entity, &mut ContactGlow)>,
+    mut commands: Commands,
+) {
+    for (entity, mut glow) in &mut glows {
+        match (tracker.is_touching(entity), glow.light) {
+            (true, None) => {
+                let light = commands
+                    .spawn(PointLightBundle {
+                        point_light: PointLight {
+                            color: glow.color,
+                            intensity: glow.intensity,
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .id();
+                commands.entity(entity).add_child(light);
+                glow.light = Some(light);
+            }
+            (false, Some(light)) => {
+                commands.entity(light).despawn_recursive();
+                glow.light = None;
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+
+const EPSILON: f32 = 0.001;
+
+trait ZeroCheck {
+    fn almost_zero(&self) -> bool;
+}
+
+impl ZeroCheck for Vec3 {
+    fn almost_zero(&self) -> bool {
+        self.x.abs() < EPSILON && self.y.abs() < EPSILON && self.z.abs() < EPSILON
+    }
+}
+
+pub fn rotation_between(from: Vec3, to: Vec3) -> Quat {
+    if from.almost_zero() || to.almost_zero() {
+        return Quat::IDENTITY;
+    }
+    let normalized_a = from.normalize();
+    let normalized_b = to.normalize();
+    let dot_product = normalized_a.dot(normalized_b);
+
+    if dot_product >= 1.0 {
+        // Vectors are already aligned, no rotation needed.
+        Quat::IDENTITY
+    } else if dot_product <= -1.0 {
+        // Vectors are opposite, a 180-degree rotation is needed.
+        // Choose any axis perpendicular to `a` for the rotation.
+        let axis = Vec3::X.cross(normalized_a).normalize();
+        Quat::from_axis_angle(axis, std::f32::consts::PI)
+    } else {
+        // General case: Calculate the rotation axis and angle.
+        let axis = normalized_a.cross(normalized_b).normalize();
+        let angle = dot_product.acos();
+        Quat::from_axis_angle(axis, angle)
+    }
+}
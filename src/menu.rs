@@ -0,0 +1,270 @@
+use bevy::prelude::*;
+
+use crate::loading::LoadStatus;
+use crate::loading::LoadingProgress;
+use crate::scenario::Scenario;
+use crate::scenario::ScenarioAssets;
+use crate::scenario::SelectedScenario;
+use crate::session::in_session;
+use crate::session::skipping_menu;
+use crate::session::RunCommand;
+use crate::session::StateScoped;
+use crate::State;
+use crate::Stats;
+
+/// Screens for the states of a session: the [`LoadingProgress`] of every
+/// asset, or why loading failed, a menu picking among the
+/// [`ScenarioAssets::scenarios`], a pause overlay and the results of a run.
+/// During a run, `pause_key` pauses and resumes, `restart_key` starts over
+/// and `menu_key` goes back to the menu.
+///
+/// Needs a window and a camera to draw the UI on, so it isn't part of
+/// [`ShootingRangePlugin`](crate::ShootingRangePlugin).
+#[derive(Clone, Debug)]
+pub struct MenuPlugin {
+    pub pause_key: KeyCode,
+    pub restart_key: KeyCode,
+    pub menu_key: KeyCode,
+}
+
+impl Default for MenuPlugin {
+    fn default() -> Self {
+        Self {
+            pause_key: KeyCode::KeyP,
+            restart_key: KeyCode::KeyR,
+            menu_key: KeyCode::KeyM,
+        }
+    }
+}
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(MenuKeys(self.clone()))
+            .add_systems(OnEnter(State::Load), spawn_loading_screen)
+            .add_systems(OnEnter(State::LoadFailed), spawn_load_errors)
+            .add_systems(OnEnter(State::Menu), spawn_menu.run_if(not(skipping_menu)))
+            .add_systems(OnEnter(State::Paused), spawn_pause_overlay)
+            .add_systems(OnEnter(State::Results), spawn_results)
+            .add_systems(
+                Update,
+                (
+                    show_loading_progress.run_if(in_state(State::Load)),
+                    pick_scenario.run_if(in_state(State::Menu)),
+                    send_run_commands.run_if(in_session),
+                ),
+            );
+    }
+}
+
+#[derive(Resource, Deref)]
+struct MenuKeys(MenuPlugin);
+
+/// Lists the assets being loaded.
+#[derive(Component)]
+struct LoadingList;
+
+/// Selects its scenario and starts a run when pressed.
+#[derive(Component)]
+struct ScenarioButton(Handle<Scenario>);
+
+const BUTTON: Color = Color::rgb(0.15, 0.15, 0.15);
+const BUTTON_HOVERED: Color = Color::rgb(0.3, 0.3, 0.3);
+
+/// The root of the UI shown in `state`, despawned when leaving it.
+fn screen(commands: &mut Commands, state: State, background: Color) -> Entity {
+    commands
+        .spawn((
+            Name::new("screen"),
+            StateScoped(state),
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(12.0),
+                    ..default()
+                },
+                background_color: background.into(),
+                ..default()
+            },
+        ))
+        .id()
+}
+
+fn text(value: impl Into<String>, size: f32) -> TextBundle {
+    TextBundle::from_section(
+        value,
+        TextStyle {
+            font_size: size,
+            color: Color::WHITE,
+            ..default()
+        },
+    )
+}
+
+fn spawn_loading_screen(mut commands: Commands) {
+    let root = screen(&mut commands, State::Load, Color::BLACK);
+    commands.entity(root).with_children(|screen| {
+        screen.spawn((text("Loading", 18.0), LoadingList));
+    });
+}
+
+fn show_loading_progress(
+    progress: Res<LoadingProgress>,
+    mut lists: Query<&mut Text, With<LoadingList>>,
+) {
+    if !progress.is_changed() {
+        return;
+    }
+    let mut lines = vec![format!(
+        "Loading {}/{} assets",
+        progress.loaded(),
+        progress.assets.len()
+    )];
+    lines.extend(progress.assets.iter().map(|asset| {
+        let status = match asset.status {
+            LoadStatus::Loading => "...",
+            LoadStatus::Loaded => "done",
+            LoadStatus::Failed => "failed",
+        };
+        format!("{}  {status}", asset.path)
+    }));
+    for mut list in &mut lists {
+        list.sections[0].value = lines.join("\n");
+    }
+}
+
+fn spawn_load_errors(progress: Res<LoadingProgress>, mut commands: Commands) {
+    let root = screen(&mut commands, State::LoadFailed, Color::BLACK);
+    commands.entity(root).with_children(|screen| {
+        screen.spawn(text("Loading failed", 32.0));
+        for error in &progress.errors {
+            // Failures to load sub-assets list all the others, which is long.
+            screen.spawn(text(error.clone(), 18.0).with_style(Style {
+                max_width: Val::Percent(90.0),
+                ..default()
+            }));
+        }
+    });
+}
+
+fn spawn_menu(assets: Res<ScenarioAssets>, asset_server: Res<AssetServer>, mut commands: Commands) {
+    let root = screen(&mut commands, State::Menu, Color::BLACK);
+    commands.entity(root).with_children(|screen| {
+        screen.spawn(text("Pick a scenario", 32.0));
+        for scenario in &assets.scenarios {
+            let name = asset_server
+                .get_path(scenario.id())
+                .and_then(|path| Some(path.path().file_name()?.to_string_lossy().into_owned()))
+                .unwrap_or_else(|| "scenario".into());
+            let name = name.trim_end_matches(".scenario.ron").to_owned();
+            screen
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            width: Val::Px(240.0),
+                            padding: UiRect::all(Val::Px(8.0)),
+                            justify_content: JustifyContent::Center,
+                            ..default()
+                        },
+                        background_color: BUTTON.into(),
+                        ..default()
+                    },
+                    ScenarioButton(scenario.clone()),
+                ))
+                .with_children(|button| {
+                    button.spawn(text(name, 24.0));
+                });
+        }
+    });
+}
+
+fn spawn_pause_overlay(keys: Res<MenuKeys>, mut commands: Commands) {
+    let root = screen(
+        &mut commands,
+        State::Paused,
+        Color::rgba(0.0, 0.0, 0.0, 0.5),
+    );
+    commands.entity(root).with_children(|screen| {
+        screen.spawn(text("Paused", 32.0));
+        screen.spawn(text(
+            format!(
+                "{:?} resumes, {:?} restarts, {:?} goes to the menu",
+                keys.pause_key, keys.restart_key, keys.menu_key
+            ),
+            18.0,
+        ));
+    });
+}
+
+fn spawn_results(stats: Res<Stats>, keys: Res<MenuKeys>, mut commands: Commands) {
+    let root = screen(
+        &mut commands,
+        State::Results,
+        Color::rgba(0.0, 0.0, 0.0, 0.5),
+    );
+    commands.entity(root).with_children(|screen| {
+        screen.spawn(text("Every target destroyed", 32.0));
+        screen.spawn(text(
+            format!(
+                "{} shots fired, {} collisions",
+                stats.shots_fired, stats.collisions
+            ),
+            24.0,
+        ));
+        screen.spawn(text(
+            format!(
+                "{:?} restarts, {:?} goes to the menu",
+                keys.restart_key, keys.menu_key
+            ),
+            18.0,
+        ));
+    });
+}
+
+type ScenarioButtons<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Interaction,
+        &'static ScenarioButton,
+        &'static mut BackgroundColor,
+    ),
+    Changed<Interaction>,
+>;
+
+fn pick_scenario(
+    mut buttons: ScenarioButtons,
+    mut run: EventWriter<RunCommand>,
+    mut commands: Commands,
+) {
+    for (interaction, button, mut background) in &mut buttons {
+        match interaction {
+            Interaction::Pressed => {
+                commands.insert_resource(SelectedScenario(button.0.clone()));
+                run.send(RunCommand::Start);
+            }
+            Interaction::Hovered => *background = BUTTON_HOVERED.into(),
+            Interaction::None => *background = BUTTON.into(),
+        }
+    }
+}
+
+fn send_run_commands(
+    keys: Res<MenuKeys>,
+    input: Res<ButtonInput<KeyCode>>,
+    mut run: EventWriter<RunCommand>,
+) {
+    let commands = [
+        (keys.pause_key, RunCommand::TogglePause),
+        (keys.restart_key, RunCommand::Restart),
+        (keys.menu_key, RunCommand::Menu),
+    ];
+    for (key, command) in commands {
+        if input.just_pressed(key) {
+            run.send(command);
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+
+use crate::layers::CollisionLayerName;
+use crate::layers::CollisionMatrix;
+
+/// Where parked entities wait, far away from anything they could run into.
+pub const PARKING_SPOT: Vec3 = Vec3::new(0.0, -10_000.0, 0.0);
+
+/// Collects parked entities marked with `T` into a [`Pool<T>`].
+///
+/// Filling the pool is up to the plugin owning `T`: it spawns entities with
+/// [`Pooled`] and [`park`]s them, and takes them back out with
+/// [`Pool::acquire`] and [`unpark`].
+pub struct PoolPlugin<T>(PhantomData<fn() -> T>);
+
+impl<T> Default for PoolPlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Component> Plugin for PoolPlugin<T> {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<ParkingPlugin>() {
+            app.add_plugins(ParkingPlugin);
+        }
+        app.init_resource::<Pool<T>>()
+            .add_systems(Update, collect_parked::<T>);
+    }
+}
+
+/// Keeps colliders of parked entities disabled, whichever pool they are in.
+struct ParkingPlugin;
+
+impl Plugin for ParkingPlugin {
+    fn build(&self, app: &mut App) {
+        // Colliders built or re-layered while parked would otherwise come back
+        // to life, so this runs right before physics.
+        app.add_systems(
+            PostUpdate,
+            disable_parked_colliders.before(PhysicsSet::Prepare),
+        );
+    }
+}
+
+/// Parked entities marked with `T`, ready to be reused.
+#[derive(Resource)]
+pub struct Pool<T> {
+    parked: Vec<Entity>,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for Pool<T> {
+    fn default() -> Self {
+        Self {
+            parked: Vec::new(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Pool<T> {
+    /// Takes a parked entity out of the pool. It stays hidden and inert until
+    /// [`unpark`]ed.
+    pub fn acquire(&mut self) -> Option<Entity> {
+        self.parked.pop()
+    }
+
+    pub fn len(&self) -> usize {
+        self.parked.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parked.is_empty()
+    }
+}
+
+/// Marks entities owned by a [`Pool`]. [`DelayedDespawn`](crate::despawn::DelayedDespawn)
+/// parks them instead of despawning them.
+#[derive(Component, Default, Debug)]
+pub struct Pooled;
+
+/// Marks pooled entities waiting to be reused.
+#[derive(Component, Debug)]
+pub struct Parked;
+
+/// Hides `entity`, stops it, moves it to the [`PARKING_SPOT`] and disables the
+/// colliders below it. Use as an [`EntityCommand`](bevy::ecs::system::EntityCommand).
+///
+/// Dynamic bodies are made kinematic so gravity doesn't carry them away.
+pub fn park(entity: Entity, world: &mut World) {
+    let descendants = descendants(world, entity);
+    let Some(mut parked) = world.get_entity_mut(entity) else {
+        return;
+    };
+    parked.insert((Parked, Visibility::Hidden));
+    if let Some(mut transform) = parked.get_mut::<Transform>() {
+        transform.translation = PARKING_SPOT;
+    }
+    if let Some(mut velocity) = parked.get_mut::<LinearVelocity>() {
+        velocity.0 = Vec3::ZERO;
+    }
+    if let Some(mut velocity) = parked.get_mut::<AngularVelocity>() {
+        velocity.0 = Vec3::ZERO;
+    }
+    if let Some(mut body) = parked.get_mut::<RigidBody>() {
+        if body.is_dynamic() {
+            *body = RigidBody::Kinematic;
+        }
+    }
+    for descendant in descendants {
+        if let Some(mut layers) = world.get_mut::<CollisionLayers>(descendant) {
+            *layers = CollisionLayers::NONE;
+        }
+    }
+}
+
+/// Shows a [`park`]ed `entity` again and restores its colliders' layers from
+/// the [`CollisionMatrix`]. Its transform, velocity and rigid body type are
+/// left to the caller.
+pub fn unpark(entity: Entity, world: &mut World) {
+    let descendants = descendants(world, entity);
+    let layers: Vec<_> = match world.get_resource::<CollisionMatrix>() {
+        Some(matrix) => descendants
+            .into_iter()
+            .filter_map(|descendant| {
+                let name = world.get::<CollisionLayerName>(descendant)?;
+                Some((descendant, matrix.layers(name)?))
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+    let Some(mut unparked) = world.get_entity_mut(entity) else {
+        return;
+    };
+    unparked.remove::<Parked>().insert(Visibility::Inherited);
+    for (descendant, layers) in layers {
+        world.entity_mut(descendant).insert(layers);
+    }
+}
+
+/// `entity` and everything below it.
+fn descendants(world: &World, entity: Entity) -> Vec<Entity> {
+    let mut found = vec![entity];
+    let mut i = 0;
+    while let Some(&current) = found.get(i) {
+        if let Some(children) = world.get::<Children>(current) {
+            found.extend(children);
+        }
+        i += 1;
+    }
+    found
+}
+
+fn collect_parked<T: Component>(
+    parked: Query<Entity, (With<T>, Added<Parked>)>,
+    mut despawned: RemovedComponents<Pooled>,
+    mut pool: ResMut<Pool<T>>,
+) {
+    pool.parked.extend(&parked);
+    for entity in despawned.read() {
+        pool.parked.retain(|&e| e != entity);
+    }
+}
+
+fn disable_parked_colliders(
+    mut colliders: Query<(Entity, &mut CollisionLayers), Changed<CollisionLayers>>,
+    parents: Query<&Parent>,
+    parked: Query<(), With<Parked>>,
+) {
+    for (entity, mut layers) in &mut colliders {
+        let is_parked = std::iter::once(entity)
+            .chain(parents.iter_ancestors(entity))
+            .any(|e| parked.contains(e));
+        if is_parked && *layers != CollisionLayers::NONE {
+            *layers = CollisionLayers::NONE;
+        }
+    }
+}
@@ -0,0 +1,289 @@
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_scene_hook::HookedSceneBundle;
+use bevy_scene_hook::SceneHook;
+use bevy_xpbd_3d::components::LinearVelocity;
+use bevy_xpbd_3d::components::RigidBody;
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::body::BodySettings;
+use crate::despawn::DelayedDespawn;
+use crate::extras::configure_from_extras;
+use crate::health::Damage;
+use crate::hitscan::Hitscan;
+use crate::math::rotation_between;
+use crate::pool::park;
+use crate::pool::unpark;
+use crate::pool::Pool;
+use crate::pool::PoolPlugin;
+use crate::pool::Pooled;
+use crate::session::StateScoped;
+use crate::session::START_RUN;
+use crate::BlenderAssets;
+use crate::State;
+use crate::Stats;
+
+/// Fires projectiles from every [`Launcher`] while in [`State::Play`], and
+/// spawns `default_launcher` when a run starts. Launchers also fire at the
+/// targets of [`FireAt`] events.
+#[derive(Clone, Debug)]
+pub struct ProjectilePlugin {
+    /// A launcher at the origin on top of the [`Scenario`]'s, handy for
+    /// ranges set up in code.
+    ///
+    /// [`Scenario`]: crate::scenario::Scenario
+    pub default_launcher: Option<Launcher>,
+    /// Ammo scenes instantiated up front and reused by launchers firing
+    /// [`BlenderAssets::ammo`]. The pool grows when they are all in flight.
+    pub pool_size: usize,
+}
+
+impl Default for ProjectilePlugin {
+    fn default() -> Self {
+        Self {
+            default_launcher: None,
+            pool_size: 8,
+        }
+    }
+}
+
+impl Plugin for ProjectilePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Launcher>()
+            .add_event::<FireAt>()
+            .add_plugins(PoolPlugin::<Projectile>::default())
+            .insert_resource(DefaultLauncher(self.default_launcher.clone()))
+            .insert_resource(PoolSize(self.pool_size))
+            .add_systems(START_RUN, (spawn_default_launcher, fill_projectile_pool))
+            .add_systems(Update, fire.run_if(in_state(State::Play)));
+    }
+}
+
+/// Marks projectiles fired by a [`Launcher`].
+#[derive(Component, Debug)]
+pub struct Projectile;
+
+/// When a projectile was last fired, in seconds of [`Time::elapsed_seconds`].
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct FiredAt(pub f32);
+
+/// Fires projectile scenes from its entity's transform, or rays if it also
+/// has a [`Hitscan`]: periodically while `auto_fire` is on, and whenever a
+/// [`FireAt`] event asks for it.
+#[derive(Component, Reflect, Deserialize, Clone, Debug)]
+#[reflect(Component)]
+#[serde(default)]
+pub struct Launcher {
+    /// Fire on a timer, rather than only at [`FireAt`] targets.
+    pub auto_fire: bool,
+    /// Bursts per second.
+    pub fire_rate: f32,
+    /// Where projectiles spawn, in the launcher's local space.
+    pub muzzle_offset: Vec3,
+    /// Initial projectile velocity, in the launcher's local space. Shots at a
+    /// [`FireAt`] target keep its speed.
+    pub velocity: Vec3,
+    /// Half-angle in radians of the cone shots are randomly spread over.
+    pub spread: f32,
+    /// Projectiles fired at once.
+    pub burst: u32,
+    /// Scene to fire, [`BlenderAssets::ammo`] if `None`.
+    #[serde(skip)]
+    pub projectile: Option<Handle<Scene>>,
+    pub scale: f32,
+    /// Seconds before a fired projectile is despawned.
+    pub lifetime: f32,
+    pub damage: Damage,
+    /// Physical response of the projectiles, on top of the collider settings
+    /// in their scene's [`NodeExtras`](crate::extras::NodeExtras).
+    pub body: BodySettings,
+    /// Time since the last burst. Its duration follows `fire_rate`.
+    #[serde(skip)]
+    pub cooldown: Timer,
+}
+
+impl Default for Launcher {
+    fn default() -> Self {
+        Self {
+            auto_fire: true,
+            fire_rate: 0.25,
+            muzzle_offset: Vec3::ZERO,
+            velocity: Vec3::new(10.0, 10.0, 0.0),
+            spread: 0.0,
+            burst: 1,
+            projectile: None,
+            scale: 2.0,
+            lifetime: 8.0,
+            damage: Damage::default(),
+            body: BodySettings::default(),
+            cooldown: Timer::default(),
+        }
+    }
+}
+
+impl Launcher {
+    /// Advances the launcher's clock and returns how many bursts are due, more
+    /// than one when the fire rate outpaces the frame rate.
+    fn tick(&mut self, delta: Duration) -> u32 {
+        let interval = Duration::from_secs_f32(1.0 / self.fire_rate.max(f32::EPSILON));
+        if self.cooldown.duration() != interval {
+            // Keeps the elapsed time, so tuning the rate doesn't reset the cooldown.
+            self.cooldown.set_duration(interval);
+            self.cooldown.set_mode(TimerMode::Repeating);
+        }
+        self.cooldown.tick(delta).times_finished_this_tick()
+    }
+
+    /// Advances the launcher's clock and returns the world space velocity of
+    /// every burst fired this frame: one per auto-fire interval elapsed, and
+    /// one towards each of the `targets`.
+    pub fn trigger(
+        &mut self,
+        delta: Duration,
+        transform: &GlobalTransform,
+        targets: impl IntoIterator<Item = Vec3>,
+    ) -> Vec<Vec3> {
+        let (_, rotation, _) = transform.to_scale_rotation_translation();
+        let muzzle = transform.transform_point(self.muzzle_offset);
+        let speed = self.velocity.length();
+        let due = self.tick(delta);
+        let mut bursts = Vec::new();
+        if self.auto_fire {
+            bursts.extend(std::iter::repeat_n(rotation * self.velocity, due as usize));
+        }
+        bursts.extend(
+            targets
+                .into_iter()
+                .map(|target| (target - muzzle).normalize_or_zero() * speed),
+        );
+        bursts
+    }
+}
+
+/// Makes `launcher`, or every launcher if `None`, fire a burst towards
+/// `target`, whether or not it auto-fires.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub struct FireAt {
+    pub launcher: Option<Entity>,
+    pub target: Vec3,
+}
+
+impl FireAt {
+    /// The targets of `requests` meant for `launcher`.
+    pub(crate) fn targets(
+        requests: &[FireAt],
+        launcher: Entity,
+    ) -> impl Iterator<Item = Vec3> + '_ {
+        requests
+            .iter()
+            .filter(move |request| request.launcher.is_none_or(|l| l == launcher))
+            .map(|request| request.target)
+    }
+}
+
+#[derive(Resource)]
+struct DefaultLauncher(Option<Launcher>);
+
+#[derive(Resource)]
+struct PoolSize(usize);
+
+fn spawn_default_launcher(mut commands: Commands, default_launcher: Res<DefaultLauncher>) {
+    if let Some(launcher) = &default_launcher.0 {
+        commands.spawn((
+            Name::new("launcher"),
+            SpatialBundle::default(),
+            launcher.clone(),
+            StateScoped(State::Play),
+        ));
+    }
+}
+
+fn projectile(scene: Handle<Scene>) -> impl Bundle {
+    (
+        HookedSceneBundle {
+            scene: SceneBundle { scene, ..default() },
+            hook: SceneHook::new(configure_from_extras),
+        },
+        RigidBody::Kinematic,
+        LinearVelocity::ZERO,
+        Projectile,
+        StateScoped(State::Play),
+    )
+}
+
+fn fill_projectile_pool(
+    mut commands: Commands,
+    assets: Res<BlenderAssets>,
+    pool_size: Res<PoolSize>,
+) {
+    for _ in 0..pool_size.0 {
+        commands
+            .spawn((projectile(assets.ammo.clone_weak()), Pooled))
+            .add(park);
+    }
+}
+
+fn fire(
+    time: Res<Time>,
+    mut launchers: Query<(Entity, &mut Launcher, &GlobalTransform), Without<Hitscan>>,
+    mut requests: EventReader<FireAt>,
+    mut pool: ResMut<Pool<Projectile>>,
+    mut stats: ResMut<Stats>,
+    mut commands: Commands,
+    assets: Res<BlenderAssets>,
+) {
+    let mut rng = rand::thread_rng();
+    let requests: Vec<FireAt> = requests.read().copied().collect();
+    for (entity, mut launcher, transform) in &mut launchers {
+        let targets = FireAt::targets(&requests, entity);
+        let bursts = launcher.trigger(time.delta(), transform, targets);
+        let muzzle = transform.transform_point(launcher.muzzle_offset);
+        for burst in bursts {
+            info!("fire ammo");
+            for _ in 0..launcher.burst {
+                let velocity = spread(burst, launcher.spread, &mut rng);
+                stats.shots_fired += 1;
+                // Only the default ammo is pooled, other scenes are spawned fresh.
+                let mut entity = match &launcher.projectile {
+                    Some(scene) => commands.spawn(projectile(scene.clone())),
+                    None => match pool.acquire() {
+                        Some(parked) => {
+                            let mut entity = commands.entity(parked);
+                            entity.add(unpark);
+                            entity
+                        }
+                        None => commands.spawn((projectile(assets.ammo.clone_weak()), Pooled)),
+                    },
+                };
+                entity.insert((
+                    Transform::from_translation(muzzle)
+                        .with_scale(Vec3::splat(launcher.scale))
+                        .with_rotation(rotation_between(Vec3::Y, velocity)),
+                    LinearVelocity(velocity),
+                    launcher.damage,
+                    DelayedDespawn::after(launcher.lifetime),
+                    FiredAt(time.elapsed_seconds()),
+                ));
+                entity.add(launcher.body);
+            }
+        }
+    }
+}
+
+/// Rotates `velocity` by a random angle of at most `half_angle` radians,
+/// uniformly over the cone's spherical cap.
+pub(crate) fn spread(velocity: Vec3, half_angle: f32, rng: &mut impl Rng) -> Vec3 {
+    if half_angle <= 0.0 || velocity == Vec3::ZERO {
+        return velocity;
+    }
+    let direction = velocity.normalize();
+    let cos_theta = rng.gen_range(half_angle.cos()..=1.0);
+    let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
+    let phi = rng.gen_range(0.0..TAU);
+    let (u, v) = direction.any_orthonormal_pair();
+    let offset = (u * phi.cos() + v * phi.sin()) * sin_theta;
+    (direction * cos_theta + offset) * velocity.length()
+}
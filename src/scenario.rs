@@ -0,0 +1,465 @@
+use std::fmt;
+
+use bevy::asset::io::Reader;
+use bevy::asset::AssetLoader;
+use bevy::asset::AsyncReadExt;
+use bevy::asset::LoadContext;
+use bevy::asset::LoadDirectError;
+use bevy::prelude::*;
+use bevy::utils::BoxedFuture;
+use bevy_asset_loader::asset_collection::AssetCollection;
+use bevy_asset_loader::dynamic_asset::DynamicAsset;
+use bevy_asset_loader::dynamic_asset::DynamicAssetType;
+use bevy_asset_loader::dynamic_asset::DynamicAssets;
+use bevy_scene_hook::HookedSceneBundle;
+use bevy_scene_hook::SceneHook;
+use serde::Deserialize;
+
+use crate::body::BodySettings;
+use crate::camera::CameraTarget;
+use crate::camera::OrbitCamera;
+use crate::extras::collider_strategy;
+use crate::extras::configure_with_override;
+use crate::extras::ColliderOverride;
+use crate::health::Health;
+use crate::hitscan::Hitscan;
+use crate::layers::CollisionMatrix;
+use crate::layers::COLLISION_MATRIX;
+use crate::loading::LoadingProgress;
+use crate::marker::ContactGlow;
+use crate::projectile::Launcher;
+use crate::session::StateScoped;
+use crate::session::START_RUN;
+use crate::State;
+
+/// Loads `.scenario.ron` files as [`Scenario`]s, builds the
+/// [`SelectedScenario`] when a run starts and rebuilds it whenever its file
+/// changes. The one at `path` is selected until another is picked in the menu.
+///
+/// The scenarios are part of the [`ScenarioAssets`] collection, so the loading
+/// state waits for them and the scenes they name.
+#[derive(Clone, Debug)]
+pub struct ScenarioPlugin {
+    /// Asset path of the scenario.
+    pub path: String,
+}
+
+impl Default for ScenarioPlugin {
+    fn default() -> Self {
+        Self {
+            path: "scenarios/default.scenario.ron".into(),
+        }
+    }
+}
+
+impl Plugin for ScenarioPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Scenario>()
+            .register_asset_loader(ScenarioLoader)
+            .init_resource::<DynamicAssets>()
+            .add_systems(Update, track_scenes.run_if(in_state(State::Load)))
+            .add_systems(
+                OnTransition {
+                    from: State::Load,
+                    to: State::Menu,
+                },
+                select_default_scenario,
+            )
+            .add_systems(START_RUN, spawn_scenario)
+            .add_systems(Update, rebuild_scenario.run_if(in_state(State::Play)));
+        app.world.resource_mut::<DynamicAssets>().register_asset(
+            "scenario",
+            Box::new(ScenarioFile {
+                path: self.path.clone(),
+            }),
+        );
+    }
+}
+
+#[derive(AssetCollection, Resource)]
+pub struct ScenarioAssets {
+    #[asset(key = "scenario")]
+    pub scenario: Handle<Scenario>,
+    /// Every scenario there is to pick from, including `scenario`.
+    #[asset(path = "scenarios", collection(typed))]
+    pub scenarios: Vec<Handle<Scenario>>,
+}
+
+/// The scenario built when the next run starts.
+#[derive(Resource, Clone, Debug)]
+pub struct SelectedScenario(pub Handle<Scenario>);
+
+/// The file behind the `scenario` key of [`ScenarioAssets`].
+#[derive(Debug)]
+struct ScenarioFile {
+    path: String,
+}
+
+impl DynamicAsset for ScenarioFile {
+    fn load(&self, asset_server: &AssetServer) -> Vec<UntypedHandle> {
+        vec![asset_server.load::<Scenario>(&self.path).untyped()]
+    }
+
+    fn build(&self, world: &mut World) -> Result<DynamicAssetType, anyhow::Error> {
+        let handle = world.resource::<AssetServer>().load::<Scenario>(&self.path);
+        Ok(DynamicAssetType::Single(handle.untyped()))
+    }
+}
+
+/// A shooting range: targets to shoot at, launchers shooting at them, lights
+/// and a camera view. Every part is optional.
+///
+/// ```ron
+/// (
+///     targets: [(scene: "rock.glb#Scene0", placement: (translation: (15, 15, 0), scale: 4))],
+///     launchers: [(launcher: (velocity: (10, 10, 0)))],
+///     lights: [Directional(illuminance: 1000, placement: (rotation: (0, 45, 0)))],
+///     camera: Some((distance: 100)),
+/// )
+/// ```
+#[derive(Asset, TypePath, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct Scenario {
+    pub targets: Vec<TargetSpec>,
+    pub launchers: Vec<LauncherSpec>,
+    pub lights: Vec<LightSpec>,
+    /// Where cameras with an [`OrbitCamera`] start, if anywhere else.
+    pub camera: Option<OrbitCamera>,
+    /// The scenes of `targets`, in order, loaded along with the scenario.
+    #[serde(skip)]
+    #[dependency]
+    pub scenes: Vec<Handle<Scene>>,
+}
+
+impl Scenario {
+    /// Checks what the file's syntax doesn't, with layers looked up in
+    /// `matrix`.
+    fn validate(&self, matrix: &CollisionMatrix) -> Result<(), String> {
+        let unknown_layer = |layer: &String| matrix.mask(layer).is_none();
+        for (i, target) in self.targets.iter().enumerate() {
+            if target.scene.is_empty() {
+                return Err(format!("target {i} has no scene"));
+            }
+            if target.health <= 0.0 {
+                return Err(format!("target {i} has no health"));
+            }
+            let colliders = &target.colliders;
+            if let Some(collider) = &colliders.collider {
+                if collider_strategy(collider).is_none() {
+                    return Err(format!("target {i} has unknown collider {collider:?}"));
+                }
+            }
+            if let Some(layer) = colliders.layer.as_ref().filter(|l| unknown_layer(l)) {
+                return Err(format!("target {i} is on unknown layer {layer:?}"));
+            }
+        }
+        for (i, spec) in self.launchers.iter().enumerate() {
+            if let Some(hitscan) = spec.hitscan.as_ref().filter(|h| unknown_layer(&h.layer)) {
+                let layer = &hitscan.layer;
+                return Err(format!(
+                    "launcher {i} casts rays on unknown layer {layer:?}"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A scene spawned as a target with [`Health`], configured from its
+/// [`NodeExtras`](crate::extras::NodeExtras).
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct TargetSpec {
+    /// Asset path of the scene, such as `rock.glb#Scene0`.
+    pub scene: String,
+    pub placement: Placement,
+    pub body: BodySettings,
+    /// Replaces the collider and layer the scene's nodes ask for.
+    pub colliders: ColliderOverride,
+    pub health: f32,
+}
+
+impl Default for TargetSpec {
+    fn default() -> Self {
+        Self {
+            scene: String::new(),
+            placement: Placement::default(),
+            body: BodySettings::default(),
+            colliders: ColliderOverride::default(),
+            health: 200.0,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct LauncherSpec {
+    pub placement: Placement,
+    pub launcher: Launcher,
+    /// Casts rays instead of firing projectiles.
+    pub hitscan: Option<Hitscan>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub enum LightSpec {
+    /// Lights the whole scene along the placement's forward axis.
+    Directional {
+        /// Lux.
+        illuminance: f32,
+        #[serde(default)]
+        placement: Placement,
+    },
+    Point {
+        /// Lumens.
+        intensity: f32,
+        range: f32,
+        translation: Vec3,
+    },
+}
+
+/// A [`Transform`] that is easier to write by hand.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(default)]
+pub struct Placement {
+    pub translation: Vec3,
+    /// Euler angles in degrees, applied in X, Y, Z order.
+    pub rotation: Vec3,
+    /// Uniform scale.
+    pub scale: f32,
+}
+
+impl Default for Placement {
+    fn default() -> Self {
+        Self {
+            translation: Vec3::ZERO,
+            rotation: Vec3::ZERO,
+            scale: 1.0,
+        }
+    }
+}
+
+impl From<Placement> for Transform {
+    fn from(placement: Placement) -> Self {
+        let [x, y, z] = placement.rotation.to_array().map(f32::to_radians);
+        Transform {
+            translation: placement.translation,
+            rotation: Quat::from_euler(EulerRot::XYZ, x, y, z),
+            scale: Vec3::splat(placement.scale),
+        }
+    }
+}
+
+/// Marks the entities built from the [`Scenario`], which are replaced when it
+/// changes. They are also scoped to [`State::Play`], so they go when the run ends.
+#[derive(Component, Debug)]
+pub struct FromScenario;
+
+#[derive(Default)]
+struct ScenarioLoader;
+
+impl AssetLoader for ScenarioLoader {
+    type Asset = Scenario;
+    type Settings = ();
+    type Error = ScenarioLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Scenario, ScenarioLoaderError>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let mut scenario: Scenario = ron::de::from_bytes(&bytes)?;
+            // Also reloads the scenario when the layers change.
+            let matrix = load_context.load_direct(COLLISION_MATRIX).await?;
+            let matrix = matrix.get::<CollisionMatrix>().ok_or_else(|| {
+                ScenarioLoaderError::Invalid(format!(
+                    "{COLLISION_MATRIX} is not a collision matrix"
+                ))
+            })?;
+            scenario
+                .validate(matrix)
+                .map_err(ScenarioLoaderError::Invalid)?;
+            scenario.scenes = scenario
+                .targets
+                .iter()
+                .map(|target| load_context.load(&target.scene))
+                .collect();
+            Ok(scenario)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["scenario.ron"]
+    }
+}
+
+#[derive(Debug)]
+pub enum ScenarioLoaderError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+    Layers(LoadDirectError),
+    Invalid(String),
+}
+
+impl fmt::Display for ScenarioLoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScenarioLoaderError::Io(err) => write!(f, "cannot read scenario: {err}"),
+            ScenarioLoaderError::Ron(err) => write!(f, "invalid scenario: {err}"),
+            ScenarioLoaderError::Layers(err) => write!(f, "cannot check scenario layers: {err}"),
+            ScenarioLoaderError::Invalid(reason) => write!(f, "invalid scenario: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ScenarioLoaderError {}
+
+impl From<std::io::Error> for ScenarioLoaderError {
+    fn from(err: std::io::Error) -> Self {
+        ScenarioLoaderError::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for ScenarioLoaderError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        ScenarioLoaderError::Ron(err)
+    }
+}
+
+impl From<LoadDirectError> for ScenarioLoaderError {
+    fn from(err: LoadDirectError) -> Self {
+        ScenarioLoaderError::Layers(err)
+    }
+}
+
+/// Tracks the scenes of scenarios as they are loaded, which are only known
+/// from their file.
+fn track_scenes(
+    mut events: EventReader<AssetEvent<Scenario>>,
+    scenarios: Res<Assets<Scenario>>,
+    asset_server: Res<AssetServer>,
+    mut progress: ResMut<LoadingProgress>,
+) {
+    for event in events.read() {
+        let AssetEvent::Added { id } = event else {
+            continue;
+        };
+        for scene in scenarios.get(*id).into_iter().flat_map(|s| &s.scenes) {
+            progress.track(&asset_server, scene.clone().untyped());
+        }
+    }
+}
+
+fn select_default_scenario(assets: Res<ScenarioAssets>, mut commands: Commands) {
+    commands.insert_resource(SelectedScenario(assets.scenario.clone()));
+}
+
+fn spawn_scenario(
+    selected: Res<SelectedScenario>,
+    scenarios: Res<Assets<Scenario>>,
+    mut cameras: Query<&mut OrbitCamera>,
+    mut commands: Commands,
+) {
+    if let Some(scenario) = scenarios.get(&selected.0) {
+        build(scenario, &mut cameras, &mut commands);
+    }
+}
+
+fn rebuild_scenario(
+    mut events: EventReader<AssetEvent<Scenario>>,
+    selected: Res<SelectedScenario>,
+    scenarios: Res<Assets<Scenario>>,
+    built: Query<Entity, With<FromScenario>>,
+    mut cameras: Query<&mut OrbitCamera>,
+    mut commands: Commands,
+) {
+    let modified = events.read().any(|event| event.is_modified(&selected.0));
+    let Some(scenario) = scenarios.get(&selected.0).filter(|_| modified) else {
+        return;
+    };
+    info!("scenario modified, rebuilding");
+    for entity in &built {
+        commands.entity(entity).despawn_recursive();
+    }
+    build(scenario, &mut cameras, &mut commands);
+}
+
+fn build(scenario: &Scenario, cameras: &mut Query<&mut OrbitCamera>, commands: &mut Commands) {
+    info!("setup scene");
+    for (target, scene) in scenario.targets.iter().zip(&scenario.scenes) {
+        commands
+            .spawn((
+                Name::new(target.scene.clone()),
+                HookedSceneBundle {
+                    scene: SceneBundle {
+                        scene: scene.clone(),
+                        transform: target.placement.into(),
+                        ..default()
+                    },
+                    hook: SceneHook::new(configure_with_override(target.colliders.clone())),
+                },
+                ContactGlow::default(),
+                Health::new(target.health),
+                CameraTarget,
+                FromScenario,
+                StateScoped(State::Play),
+            ))
+            .add(target.body);
+    }
+    for spec in &scenario.launchers {
+        let mut launcher = commands.spawn((
+            Name::new("launcher"),
+            SpatialBundle::from_transform(spec.placement.into()),
+            spec.launcher.clone(),
+            FromScenario,
+            StateScoped(State::Play),
+        ));
+        if let Some(hitscan) = &spec.hitscan {
+            launcher.insert(hitscan.clone());
+        }
+    }
+    for light in &scenario.lights {
+        match *light {
+            LightSpec::Directional {
+                illuminance,
+                placement,
+            } => commands.spawn((
+                DirectionalLightBundle {
+                    directional_light: DirectionalLight {
+                        illuminance,
+                        ..default()
+                    },
+                    transform: placement.into(),
+                    ..default()
+                },
+                FromScenario,
+                StateScoped(State::Play),
+            )),
+            LightSpec::Point {
+                intensity,
+                range,
+                translation,
+            } => commands.spawn((
+                PointLightBundle {
+                    point_light: PointLight {
+                        intensity,
+                        range,
+                        ..default()
+                    },
+                    transform: Transform::from_translation(translation),
+                    ..default()
+                },
+                FromScenario,
+                StateScoped(State::Play),
+            )),
+        };
+    }
+    if let Some(camera) = scenario.camera {
+        for mut orbit in cameras.iter_mut() {
+            *orbit = camera;
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+
+use crate::health::despawn_destroyed;
+use crate::health::Destroyed;
+use crate::health::Health;
+use crate::State;
+use crate::Stats;
+
+/// Runs of the [`Scenario`](crate::scenario::Scenario): started from
+/// [`State::Menu`], paused and resumed, ended on [`State::Results`] once every
+/// target is destroyed, and driven by [`RunCommand`]s.
+///
+/// Entities carrying a [`StateScoped`] are despawned when leaving their state,
+/// so everything a run spawns, scoped to [`State::Play`], is gone before the
+/// next one starts.
+#[derive(Clone, Debug)]
+pub struct SessionPlugin {
+    /// Start the first run as soon as loading is done, rather than waiting
+    /// for a [`RunCommand::Start`] in the menu.
+    pub autostart: bool,
+}
+
+impl Default for SessionPlugin {
+    fn default() -> Self {
+        Self { autostart: true }
+    }
+}
+
+impl Plugin for SessionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<RunCommand>()
+            .insert_resource(Autostart(self.autostart))
+            .init_resource::<Restarting>()
+            .add_systems(OnEnter(State::Menu), start_from_menu.run_if(skipping_menu))
+            .add_systems(START_RUN, (reset_stats, clear_skipping_menu))
+            .add_systems(OnEnter(State::Paused), pause_physics)
+            .add_systems(OnExit(State::Paused), resume_physics)
+            .add_systems(
+                Update,
+                (
+                    run_commands,
+                    show_results
+                        .after(despawn_destroyed)
+                        .run_if(in_state(State::Play)),
+                ),
+            );
+        for state in State::ALL {
+            app.add_systems(OnExit(state.clone()), despawn_scoped(state));
+        }
+    }
+}
+
+/// Entered when a run starts from the menu, but not when it resumes from
+/// [`State::Paused`]. Systems setting up the range run here.
+pub const START_RUN: OnTransition<State> = OnTransition {
+    from: State::Menu,
+    to: State::Play,
+};
+
+/// Despawns its entity, with its descendants, when leaving the state, unless
+/// for a state [within](State::is_within) it.
+#[derive(Component, Clone, Debug, PartialEq, Eq)]
+pub struct StateScoped(pub State);
+
+/// Requests to move between the states of a session, ignored where they
+/// don't apply.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RunCommand {
+    /// Starts a run from the menu.
+    Start,
+    /// Pauses a run in play, or resumes a paused one.
+    TogglePause,
+    /// Ends the run and starts a fresh one.
+    Restart,
+    /// Ends the run and goes back to the menu.
+    Menu,
+}
+
+/// Whether [`State::Menu`] is only passed through, to autostart the first run
+/// or to restart one, so there is no menu to show.
+pub(crate) fn skipping_menu(autostart: Res<Autostart>, restarting: Res<Restarting>) -> bool {
+    autostart.0 || restarting.0
+}
+
+/// Whether a run is going on, in play, paused or showing its results.
+pub fn in_session(state: Res<bevy::prelude::State<State>>) -> bool {
+    state.in_session()
+}
+
+#[derive(Resource)]
+pub(crate) struct Autostart(bool);
+
+/// Set when a run ends to start the next one, which happens from the menu.
+#[derive(Resource, Default)]
+pub(crate) struct Restarting(bool);
+
+fn start_from_menu(mut next: ResMut<NextState<State>>) {
+    next.set(State::Play);
+}
+
+/// Both only apply to the run they start: autostarting comes from loading, and
+/// a run ending normally leads to the menu.
+fn clear_skipping_menu(mut autostart: ResMut<Autostart>, mut restarting: ResMut<Restarting>) {
+    autostart.0 = false;
+    restarting.0 = false;
+}
+
+fn run_commands(
+    mut commands: EventReader<RunCommand>,
+    state: Res<bevy::prelude::State<State>>,
+    mut restarting: ResMut<Restarting>,
+    mut next: ResMut<NextState<State>>,
+) {
+    for command in commands.read() {
+        let to = match (command, state.get()) {
+            (RunCommand::Start, State::Menu) => State::Play,
+            (RunCommand::TogglePause, State::Play) => State::Paused,
+            (RunCommand::TogglePause, State::Paused) => State::Play,
+            (RunCommand::Restart, current) if current.in_session() => {
+                restarting.0 = true;
+                State::Menu
+            }
+            (RunCommand::Menu, current) if current.in_session() => State::Menu,
+            _ => continue,
+        };
+        info!("{command:?}: {:?} -> {to:?}", state.get());
+        next.set(to);
+    }
+}
+
+fn reset_stats(mut stats: ResMut<Stats>) {
+    *stats = Stats::default();
+}
+
+fn pause_physics(mut time: ResMut<Time<Physics>>) {
+    time.pause();
+}
+
+fn resume_physics(mut time: ResMut<Time<Physics>>) {
+    time.unpause();
+}
+
+type Scoped<'w, 's> = Query<'w, 's, (Entity, &'static StateScoped)>;
+
+/// Despawns the entities scoped to `exited`, or to a state it is within, that
+/// the state entered is out of. The state entered is already current.
+fn despawn_scoped(exited: State) -> impl FnMut(Res<bevy::prelude::State<State>>, Scoped, Commands) {
+    move |entered, scoped, mut commands| {
+        let mut despawned = 0;
+        for (entity, StateScoped(scope)) in &scoped {
+            if exited.is_within(scope) && !entered.is_within(scope) {
+                commands.entity(entity).despawn_recursive();
+                despawned += 1;
+            }
+        }
+        if despawned > 0 {
+            info!("left {exited:?}, despawning {despawned} entities");
+        }
+    }
+}
+
+/// Ends play once the last target is destroyed.
+fn show_results(
+    mut destroyed: EventReader<Destroyed>,
+    targets: Query<Entity, With<Health>>,
+    mut next: ResMut<NextState<State>>,
+) {
+    let destroyed: Vec<Entity> = destroyed.read().map(|d| d.entity).collect();
+    if !destroyed.is_empty() && targets.iter().all(|target| destroyed.contains(&target)) {
+        info!("every target destroyed");
+        next.set(State::Results);
+    }
+}
@@ -0,0 +1,126 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy::utils::HashSet;
+use bevy_xpbd_3d::prelude::*;
+
+/// Tracks which rigid bodies touch each other in a [`ContactTracker`] and
+/// reports changes as [`ContactEntered`], [`ContactStayed`] and
+/// [`ContactExited`] events.
+pub struct ContactTrackerPlugin;
+
+impl Plugin for ContactTrackerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ContactTracker>()
+            .add_event::<ContactEntered>()
+            .add_event::<ContactStayed>()
+            .add_event::<ContactExited>()
+            .add_systems(Update, track_contacts);
+    }
+}
+
+/// Two rigid bodies, the lower entity first.
+pub type BodyPair = (Entity, Entity);
+
+fn body_pair(a: Entity, b: Entity) -> BodyPair {
+    (a.min(b), a.max(b))
+}
+
+/// Rigid bodies whose colliders touched in the latest physics step.
+///
+/// Pairs are made of the colliders' [`ColliderParent`]s, so a body touching
+/// another with several colliders still counts once.
+#[derive(Resource, Default, Debug)]
+pub struct ContactTracker {
+    /// When each pair started touching, in physics time.
+    since: HashMap<BodyPair, Duration>,
+    /// Physics time of the latest step seen.
+    step: Duration,
+}
+
+impl ContactTracker {
+    /// How long `a` and `b` have been touching, if they are.
+    pub fn duration(&self, a: Entity, b: Entity) -> Option<Duration> {
+        let since = self.since.get(&body_pair(a, b))?;
+        Some(self.step - *since)
+    }
+
+    /// Whether `entity` touches any other body.
+    pub fn is_touching(&self, entity: Entity) -> bool {
+        self.since.keys().any(|&(a, b)| a == entity || b == entity)
+    }
+
+    /// Every pair touching and how long it has been.
+    pub fn contacts(&self) -> impl Iterator<Item = (BodyPair, Duration)> + '_ {
+        self.since
+            .iter()
+            .map(|(&pair, &since)| (pair, self.step - since))
+    }
+}
+
+/// Two bodies started touching.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub struct ContactEntered {
+    pub bodies: BodyPair,
+}
+
+/// Two bodies are still touching after another physics step.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub struct ContactStayed {
+    pub bodies: BodyPair,
+    /// Time since they started touching.
+    pub duration: Duration,
+}
+
+/// Two bodies stopped touching.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub struct ContactExited {
+    pub bodies: BodyPair,
+    /// How long they were touching.
+    pub duration: Duration,
+}
+
+pub fn track_contacts(
+    time: Res<Time<Physics>>,
+    parents: Query<&ColliderParent>,
+    mut collisions: EventReader<Collision>,
+    mut tracker: ResMut<ContactTracker>,
+    mut entered: EventWriter<ContactEntered>,
+    mut stayed: EventWriter<ContactStayed>,
+    mut exited: EventWriter<ContactExited>,
+) {
+    // Collisions are only reported when physics steps, so a frame without a
+    // step (e.g. while paused) says nothing about who stopped touching.
+    if time.elapsed() == tracker.step {
+        collisions.clear();
+        return;
+    }
+    tracker.step = time.elapsed();
+
+    let mut touching = HashSet::new();
+    for Collision(contacts) in collisions.read() {
+        let Ok([a, b]) = parents.get_many([contacts.entity1, contacts.entity2]) else {
+            continue;
+        };
+        if a.get() != b.get() {
+            touching.insert(body_pair(a.get(), b.get()));
+        }
+    }
+
+    let step = tracker.step;
+    tracker.since.retain(|&bodies, since| {
+        let duration = step - *since;
+        if touching.remove(&bodies) {
+            stayed.send(ContactStayed { bodies, duration });
+            true
+        } else {
+            exited.send(ContactExited { bodies, duration });
+            false
+        }
+    });
+    for bodies in touching {
+        tracker.since.insert(bodies, step);
+        entered.send(ContactEntered { bodies });
+    }
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+use bevy_xpbd_test::aim::aim_point;
+use bevy_xpbd_test::pool::Parked;
+use bevy_xpbd_test::projectile::FireAt;
+use bevy_xpbd_test::projectile::Launcher;
+use bevy_xpbd_test::projectile::Projectile;
+use bevy_xpbd_test::projectile::ProjectilePlugin;
+use bevy_xpbd_test::ShootingRangePlugin;
+use bevy_xpbd_test::Stats;
+use common::*;
+
+fn ray(origin: Vec3, direction: Vec3) -> Ray3d {
+    Ray3d::new(origin, direction)
+}
+
+#[test]
+fn aims_at_the_z_plane_without_a_hit() {
+    let ray = ray(Vec3::new(3.0, 4.0, 100.0), Vec3::NEG_Z);
+    assert_eq!(aim_point(ray, None), Some(Vec3::new(3.0, 4.0, 0.0)));
+}
+
+#[test]
+fn aims_at_the_hit_before_the_plane() {
+    let ray = ray(Vec3::new(3.0, 4.0, 100.0), Vec3::NEG_Z);
+    assert_eq!(aim_point(ray, Some(90.0)), Some(Vec3::new(3.0, 4.0, 10.0)));
+}
+
+#[test]
+fn rays_along_the_plane_aim_nowhere() {
+    assert_eq!(aim_point(ray(Vec3::Z, Vec3::X), None), None);
+}
+
+#[test]
+fn launchers_without_auto_fire_only_fire_when_asked() {
+    let launcher = Launcher {
+        auto_fire: false,
+        fire_rate: 10.0,
+        ..default()
+    };
+    let speed = launcher.velocity.length();
+    let mut app = headless_app(ShootingRangePlugin {
+        projectile: ProjectilePlugin {
+            default_launcher: Some(launcher),
+            ..default()
+        },
+        ..default()
+    });
+    update_until_playing(&mut app);
+    for _ in 0..30 {
+        app.update();
+    }
+    assert_eq!(app.world.resource::<Stats>().shots_fired, 0);
+
+    let launcher = app
+        .world
+        .query_filtered::<Entity, With<Launcher>>()
+        .single(&app.world);
+    let target = Vec3::new(-5.0, 20.0, 0.0);
+    app.world.send_event(FireAt {
+        launcher: Some(launcher),
+        target,
+    });
+    app.update();
+    assert_eq!(app.world.resource::<Stats>().shots_fired, 1);
+
+    let (velocity, transform) = app
+        .world
+        .query_filtered::<(&LinearVelocity, &Transform), (With<Projectile>, Without<Parked>)>()
+        .single(&app.world);
+    let direction = target.normalize();
+    assert!((velocity.length() - speed).abs() < 1e-3, "{velocity:?}");
+    assert!(velocity.normalize().dot(direction) > 0.999, "{velocity:?}");
+    assert!((transform.rotation * Vec3::Y).dot(direction) > 0.999);
+}
@@ -0,0 +1,177 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+use bevy_xpbd_test::body::BodySettings;
+use bevy_xpbd_test::body::BodyType;
+use bevy_xpbd_test::pool::Parked;
+use bevy_xpbd_test::projectile::Launcher;
+use bevy_xpbd_test::projectile::Projectile;
+use bevy_xpbd_test::projectile::ProjectilePlugin;
+use bevy_xpbd_test::ShootingRangePlugin;
+use bevy_xpbd_test::Stats;
+use common::*;
+
+/// A range whose launcher at the origin fires a single dynamic projectile
+/// with `velocity` after one second, and returns it.
+fn fire_once(gravity: Vec3, velocity: Vec3, body: BodySettings) -> (App, Entity) {
+    let mut app = headless_app(ShootingRangePlugin {
+        gravity,
+        projectile: ProjectilePlugin {
+            default_launcher: Some(Launcher {
+                fire_rate: 1.0,
+                velocity,
+                lifetime: 10.0,
+                body,
+                ..default()
+            }),
+            ..default()
+        },
+        ..default()
+    });
+    update_until_playing(&mut app);
+    update_until(&mut app, 100, |app| {
+        app.world.resource::<Stats>().shots_fired > 0
+    });
+    let projectile = app
+        .world
+        .query_filtered::<Entity, (With<Projectile>, Without<Parked>)>()
+        .single(&app.world);
+    (app, projectile)
+}
+
+fn dynamic() -> BodySettings {
+    BodySettings {
+        body_type: BodyType::Dynamic,
+        ..default()
+    }
+}
+
+#[test]
+fn dynamic_projectiles_follow_a_ballistic_arc() {
+    let gravity = Vec3::new(0.0, -9.81, 0.0);
+    // Away from the rock.
+    let (mut app, projectile) = fire_once(gravity, Vec3::new(-10.0, 10.0, 0.0), dynamic());
+    assert_eq!(app.world.get(projectile), Some(&RigidBody::Dynamic));
+
+    let state = |app: &App| {
+        (
+            app.world.get::<Position>(projectile).unwrap().0,
+            app.world.get::<LinearVelocity>(projectile).unwrap().0,
+            app.world.resource::<Time<Physics>>().elapsed_seconds(),
+        )
+    };
+    let (start, velocity, fired) = state(&app);
+    for _ in 0..60 {
+        app.update();
+    }
+    let (position, _, now) = state(&app);
+    let t = now - fired;
+    assert!(t > 0.9, "{t}");
+    let expected = start + velocity * t + 0.5 * gravity * t * t;
+    assert!(
+        position.distance(expected) < 0.15,
+        "{position:?} != {expected:?}"
+    );
+}
+
+#[test]
+fn kinematic_projectiles_ignore_gravity() {
+    let velocity = Vec3::new(-10.0, 10.0, 0.0);
+    let (mut app, projectile) = fire_once(Vec3::NEG_Y * 9.81, velocity, default());
+    for _ in 0..30 {
+        app.update();
+    }
+    assert_eq!(app.world.get(projectile), Some(&RigidBody::Kinematic));
+    assert_eq!(
+        app.world.get::<LinearVelocity>(projectile).unwrap().0,
+        velocity
+    );
+}
+
+#[test]
+fn dynamic_projectiles_bounce_off_terrain() {
+    let (mut app, projectile) = fire_once(Vec3::ZERO, Vec3::NEG_Y * 10.0, dynamic());
+    app.world.spawn((
+        RigidBody::Static,
+        Collider::cuboid(20.0, 1.0, 20.0),
+        Restitution::new(0.6),
+        TransformBundle::from_transform(Transform::from_xyz(0.0, -6.0, 0.0)),
+    ));
+
+    // The ammo's own restitution of 0.6 comes from its glTF extras.
+    update_until(&mut app, 120, |app| {
+        app.world.get::<LinearVelocity>(projectile).unwrap().y > 0.0
+    });
+    for _ in 0..10 {
+        app.update();
+    }
+    let velocity = app.world.get::<LinearVelocity>(projectile).unwrap().0;
+    assert!((velocity.y - 6.0).abs() < 1.0, "{velocity:?}");
+}
+
+#[test]
+fn body_mass_overrides_the_colliders() {
+    let (mut app, projectile) = fire_once(
+        Vec3::ZERO,
+        Vec3::new(-10.0, 0.0, 0.0),
+        BodySettings {
+            mass: Some(3.0),
+            ..dynamic()
+        },
+    );
+    // Without it, the ammo's volume makes for a mass of over a hundred. It
+    // takes a few steps to settle, as the collider is scaled up after firing.
+    update_until(&mut app, 10, |app| {
+        (app.world.get::<Mass>(projectile).unwrap().0 - 3.0).abs() < 1e-3
+    });
+}
+
+#[test]
+fn reused_projectiles_drop_the_mass_of_their_last_launcher() {
+    let mut app = headless_app(ShootingRangePlugin {
+        projectile: ProjectilePlugin {
+            default_launcher: Some(Launcher {
+                fire_rate: 2.0,
+                velocity: Vec3::new(-10.0, 0.0, 0.0),
+                lifetime: 0.3,
+                body: dynamic(),
+                ..default()
+            }),
+            pool_size: 1,
+        },
+        ..default()
+    });
+    update_until_playing(&mut app);
+    // Fires the single pooled projectile with `mass` and returns it once its
+    // mass has settled to `expected`, or to whatever it settles to.
+    let fire = |app: &mut App, mass: Option<f32>, expected: Option<f32>| {
+        let mut launchers = app.world.query::<&mut Launcher>();
+        launchers.single_mut(&mut app.world).body.mass = mass;
+        let fired = app.world.resource::<Stats>().shots_fired;
+        update_until(app, 100, |app| {
+            app.world.resource::<Stats>().shots_fired > fired
+        });
+        let projectile = app
+            .world
+            .query_filtered::<Entity, (With<Projectile>, Without<Parked>)>()
+            .single(&app.world);
+        let mut last = f32::NAN;
+        update_until(app, 10, |app| {
+            let mass = app.world.get::<Mass>(projectile).unwrap().0;
+            let settled = match expected {
+                Some(expected) => (mass - expected).abs() < 1e-3 * expected,
+                None => mass == last,
+            };
+            last = mass;
+            settled
+        });
+        (projectile, last)
+    };
+
+    let (first, natural) = fire(&mut app, None, None);
+    assert!(natural > 3.0, "{natural}");
+    let (second, _) = fire(&mut app, Some(3.0), Some(3.0));
+    let (third, _) = fire(&mut app, None, Some(natural));
+    assert_eq!([second, third], [first, first]);
+}
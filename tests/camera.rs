@@ -0,0 +1,88 @@
+use std::f32::consts::FRAC_PI_2;
+use std::f32::consts::FRAC_PI_4;
+
+use bevy::prelude::*;
+use bevy_xpbd_test::camera::OrbitCamera;
+
+fn assert_near(a: Vec3, b: Vec3) {
+    assert!(a.distance(b) < 1e-3, "{a:?} != {b:?}");
+}
+
+#[test]
+fn default_orbit_looks_at_the_origin_from_z() {
+    let transform = OrbitCamera::default().transform();
+    assert_near(transform.translation, Vec3::new(0.0, 0.0, 100.0));
+    assert_near(*transform.forward(), Vec3::NEG_Z);
+}
+
+#[test]
+fn orbiting_keeps_the_focus_in_view() {
+    let mut orbit = OrbitCamera {
+        focus: Vec3::new(1.0, 2.0, 3.0),
+        distance: 10.0,
+        ..default()
+    };
+    orbit.orbit(Vec2::new(FRAC_PI_2, FRAC_PI_4));
+    let transform = orbit.transform();
+    assert!((transform.translation.distance(orbit.focus) - 10.0).abs() < 1e-3);
+    let towards_focus = (orbit.focus - transform.translation).normalize();
+    assert_near(*transform.forward(), towards_focus);
+    // Turned to +X and lifted above the focus.
+    assert!(transform.translation.x > orbit.focus.x);
+    assert!(transform.translation.y > orbit.focus.y);
+}
+
+#[test]
+fn orbiting_stops_short_of_the_poles() {
+    let mut orbit = OrbitCamera::default();
+    orbit.orbit(Vec2::new(0.0, 10.0));
+    assert!(orbit.pitch < FRAC_PI_2);
+    let up = orbit.transform().up();
+    assert!(up.is_finite() && up.y > 0.0, "{up:?}");
+}
+
+#[test]
+fn panning_drags_the_focus_with_the_cursor() {
+    let mut orbit = OrbitCamera::default();
+    // Across the whole height of the view at the focus plane.
+    let fov = FRAC_PI_2;
+    orbit.pan(Vec2::new(0.0, 600.0), 600.0, fov);
+    assert_near(orbit.focus, Vec3::new(0.0, 200.0, 0.0));
+    orbit.pan(Vec2::new(300.0, 0.0), 600.0, fov);
+    assert_near(orbit.focus, Vec3::new(-100.0, 200.0, 0.0));
+}
+
+#[test]
+fn zooming_scales_the_distance_but_never_reaches_the_focus() {
+    let mut orbit = OrbitCamera::default();
+    orbit.zoom(0.5);
+    assert_eq!(orbit.distance, 50.0);
+    orbit.zoom(0.0);
+    assert!(orbit.distance > 0.0);
+}
+
+#[test]
+fn framing_fits_the_box_in_the_narrower_field_of_view() {
+    let mut orbit = OrbitCamera::default();
+    let (min, max) = (Vec3::new(9.0, 9.0, -1.0), Vec3::new(11.0, 11.0, 1.0));
+    let radius = 3f32.sqrt();
+    let landscape = PerspectiveProjection {
+        fov: FRAC_PI_2,
+        aspect_ratio: 2.0,
+        ..default()
+    };
+    orbit.frame(min, max, &landscape);
+    assert_near(orbit.focus, Vec3::new(10.0, 10.0, 0.0));
+    assert!((orbit.distance - radius / FRAC_PI_4.sin()).abs() < 1e-3);
+
+    // A portrait window is narrower across, so the camera backs off further.
+    let portrait = PerspectiveProjection {
+        aspect_ratio: 0.5,
+        ..landscape
+    };
+    let mut wide = orbit;
+    wide.frame(min, max, &portrait);
+    assert!(wide.distance > orbit.distance);
+    let half_width = (FRAC_PI_4.tan() * 0.5).atan();
+    assert!((wide.distance - radius / half_width.sin()).abs() < 1e-3);
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use bevy::ecs::event::ManualEventReader;
+use bevy::prelude::*;
+use bevy_xpbd_test::health::Damage;
+use bevy_xpbd_test::impact::ImpactEvent;
+use bevy_xpbd_test::projectile::Launcher;
+use bevy_xpbd_test::projectile::ProjectilePlugin;
+use bevy_xpbd_test::ShootingRangePlugin;
+use bevy_xpbd_test::Stats;
+use common::*;
+
+#[test]
+fn fast_projectiles_never_miss() {
+    // Even each substep moves the ammo several times the rock's width.
+    let speed = 200_000.0;
+    let mut app = headless_app(ShootingRangePlugin {
+        projectile: ProjectilePlugin {
+            default_launcher: Some(Launcher {
+                fire_rate: 10.0,
+                velocity: Vec3::new(1.0, 1.0, 0.0).normalize() * speed,
+                lifetime: 0.5,
+                damage: Damage(0.0),
+                ..default()
+            }),
+            ..default()
+        },
+        ..default()
+    });
+    update_until_playing(&mut app);
+
+    // Impacts are counted towards the latest shot, as each one reaches the
+    // rock long before the next is fired.
+    let mut reader = ManualEventReader::<ImpactEvent>::default();
+    let mut hits = Vec::new();
+    update_until(&mut app, 1_000, |app| {
+        let fired = app.world.resource::<Stats>().shots_fired;
+        hits.resize(fired, 0);
+        let events = app.world.resource::<Events<ImpactEvent>>();
+        if let Some(count) = hits.last_mut() {
+            *count += reader.read(events).count();
+        }
+        fired > 20
+    });
+    // The last shot hasn't arrived yet.
+    hits.pop();
+
+    // Once per shot, never twice.
+    assert!(hits.iter().all(|&count| count == 1), "{hits:?}");
+}
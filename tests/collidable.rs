@@ -0,0 +1,176 @@
+use std::sync::Arc;
+
+use bevy::ecs::system::EntityCommand;
+use bevy::prelude::*;
+use bevy::render::mesh::MeshPlugin;
+use bevy_xpbd_3d::prelude::*;
+use bevy_xpbd_test::collidable::*;
+use bevy_xpbd_test::layers::*;
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default(), MeshPlugin))
+        .add_plugins((CollidablePlugin, CollisionMatrixPlugin));
+    app
+}
+
+fn failures(app: &App) -> Vec<ColliderBuildFailed> {
+    let events = app.world.resource::<Events<ColliderBuildFailed>>();
+    events.get_reader().read(events).cloned().collect()
+}
+
+#[test]
+fn missing_children_is_reported() {
+    let mut app = app();
+    let entity = app.world.spawn_empty().id();
+    Collidable {
+        layer: "Object".into(),
+        strategy: ColliderStrategy::Trimesh,
+    }
+    .apply(entity, &mut app.world);
+    app.update();
+
+    let failures = failures(&app);
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].entity, entity);
+    assert_eq!(failures[0].reason, CollidableError::NoChildren);
+    assert!(app.world.get::<Collider>(entity).is_none());
+}
+
+#[test]
+fn child_without_mesh_is_reported() {
+    let mut app = app();
+    let entity = app
+        .world
+        .spawn_empty()
+        .with_children(|c| {
+            c.spawn_empty();
+        })
+        .id();
+    Collidable {
+        layer: "Object".into(),
+        strategy: ColliderStrategy::Trimesh,
+    }
+    .apply(entity, &mut app.world);
+    app.update();
+
+    let failures = failures(&app);
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].reason, CollidableError::NoMesh);
+}
+
+#[test]
+fn collider_is_built_once_mesh_arrives() {
+    let mut app = app();
+    let handle = app.world.resource::<Assets<Mesh>>().reserve_handle();
+    let entity = app
+        .world
+        .spawn_empty()
+        .with_children(|c| {
+            c.spawn(handle.clone());
+        })
+        .id();
+    Collidable {
+        layer: "Ammo".into(),
+        strategy: ColliderStrategy::Trimesh,
+    }
+    .apply(entity, &mut app.world);
+    app.update();
+
+    assert!(failures(&app).is_empty());
+    assert!(app.world.get::<PendingCollidable>(entity).is_some());
+    assert!(app.world.get::<Collider>(entity).is_none());
+
+    app.world
+        .resource_mut::<Assets<Mesh>>()
+        .insert(&handle, Cuboid::default().into());
+    app.update();
+    app.update();
+
+    assert!(app.world.get::<PendingCollidable>(entity).is_none());
+    assert!(app.world.get::<Collider>(entity).is_some());
+    assert_eq!(
+        app.world.get::<CollisionLayers>(entity),
+        CollisionMatrix::default().layers("Ammo").as_ref()
+    );
+    assert_eq!(
+        app.world.get::<CollisionLayerName>(entity),
+        Some(&CollisionLayerName("Ammo".into()))
+    );
+}
+
+fn local_aabb(collider: &Collider) -> (Vec3, Vec3) {
+    let aabb = collider.shape().compute_local_aabb();
+    (aabb.mins.into(), aabb.maxs.into())
+}
+
+#[test]
+fn fitted_primitives_match_mesh_bounds() {
+    let mesh = Mesh::from(Cuboid::new(2.0, 6.0, 1.0));
+    let (min, max) = (Vec3::new(-1.0, -3.0, -0.5), Vec3::new(1.0, 3.0, 0.5));
+
+    let cuboid = ColliderStrategy::Cuboid.build(&mesh).unwrap();
+    let (cmin, cmax) = local_aabb(&cuboid);
+    assert!(cmin.abs_diff_eq(min, 1e-5) && cmax.abs_diff_eq(max, 1e-5));
+
+    let sphere = ColliderStrategy::Sphere.build(&mesh).unwrap();
+    assert_eq!(local_aabb(&sphere), (Vec3::splat(-3.0), Vec3::splat(3.0)));
+
+    // The capsule runs along y and is as wide as the x extent.
+    let capsule = ColliderStrategy::Capsule.build(&mesh).unwrap();
+    let (cmin, cmax) = local_aabb(&capsule);
+    assert!(cmin.abs_diff_eq(Vec3::new(-1.0, -3.0, -1.0), 1e-5));
+    assert!(cmax.abs_diff_eq(Vec3::new(1.0, 3.0, 1.0), 1e-5));
+}
+
+#[test]
+fn convex_strategies_build_from_mesh() {
+    let mesh = Mesh::from(Sphere::new(1.0));
+    for strategy in [
+        ColliderStrategy::Trimesh,
+        ColliderStrategy::ConvexHull,
+        ColliderStrategy::ConvexDecomposition(default()),
+    ] {
+        assert!(strategy.build(&mesh).is_some(), "{strategy:?}");
+    }
+}
+
+#[test]
+fn colliders_share_cached_shape_until_mesh_changes() {
+    let mut app = app();
+    let mesh = app
+        .world
+        .resource_mut::<Assets<Mesh>>()
+        .add(Sphere::new(1.0));
+    let spawn = |app: &mut App| {
+        let entity = app
+            .world
+            .spawn_empty()
+            .with_children(|c| {
+                c.spawn(mesh.clone());
+            })
+            .id();
+        Collidable {
+            layer: "Ammo".into(),
+            strategy: ColliderStrategy::ConvexHull,
+        }
+        .apply(entity, &mut app.world);
+        app.world.get::<Collider>(entity).unwrap().clone()
+    };
+
+    let first = spawn(&mut app);
+    let second = spawn(&mut app);
+    assert!(Arc::ptr_eq(&first.shape().0, &second.shape().0));
+    assert_eq!(app.world.resource::<ColliderCache>().len(), 1);
+
+    app.world
+        .resource_mut::<Assets<Mesh>>()
+        .insert(&mesh, Sphere::new(2.0).into());
+    // Asset events are flushed at the end of the frame and read on the next one.
+    app.update();
+    app.update();
+    assert!(app.world.resource::<ColliderCache>().is_empty());
+
+    let rebuilt = spawn(&mut app);
+    assert!(!Arc::ptr_eq(&first.shape().0, &rebuilt.shape().0));
+}
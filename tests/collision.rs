@@ -0,0 +1,102 @@
+mod common;
+
+use bevy::ecs::event::ManualEventReader;
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+use bevy_xpbd_test::layers::CollisionMatrix;
+use bevy_xpbd_test::marker::ImpactMarker;
+use bevy_xpbd_test::pool::Parked;
+use bevy_xpbd_test::projectile::Launcher;
+use bevy_xpbd_test::projectile::ProjectilePlugin;
+use bevy_xpbd_test::ShootingRangePlugin;
+use common::*;
+
+/// `collision_started` with two contact points instead of one, a unit above
+/// and below it, the upper one penetrating three times as deep.
+fn uneven_collision(entity1: Entity, entity2: Entity) -> Collision {
+    let Collision(mut contacts) = collision_started(entity1, entity2);
+    let manifold = &mut contacts.manifolds[0];
+    let contact = manifold.contacts[0];
+    manifold.contacts = vec![
+        ContactData {
+            point1: contact.point1 + Vec3::Y,
+            point2: contact.point2 + Vec3::Y,
+            penetration: 0.3,
+            ..contact
+        },
+        ContactData {
+            point1: contact.point1 - Vec3::Y,
+            point2: contact.point2 - Vec3::Y,
+            penetration: 0.1,
+            index: 1,
+            ..contact
+        },
+    ];
+    Collision(contacts)
+}
+
+fn range() -> ShootingRangePlugin {
+    ShootingRangePlugin {
+        projectile: ProjectilePlugin {
+            default_launcher: Some(Launcher {
+                fire_rate: 2.0,
+                ..default()
+            }),
+            ..default()
+        },
+        ..default()
+    }
+}
+
+#[test]
+fn ammo_collides_with_rock() {
+    let mut app = headless_app(range());
+    update_until_playing(&mut app);
+
+    let mut reader = ManualEventReader::<Collision>::default();
+    let mut collision = None;
+    update_until(&mut app, 600, |app| {
+        let events = app.world.resource::<Events<Collision>>();
+        collision = reader.read(events).next().map(|c| c.0.clone());
+        collision.is_some()
+    });
+    let contacts = collision.unwrap();
+
+    let layers = |entity| *app.world.get::<CollisionLayers>(entity).unwrap();
+    let mut pair = [layers(contacts.entity1), layers(contacts.entity2)];
+    pair.sort_by_key(|l| l.memberships.0);
+    let matrix = app.world.resource::<CollisionMatrix>();
+    assert_eq!(
+        pair,
+        [
+            matrix.layers("Ammo").unwrap(),
+            matrix.layers("Object").unwrap()
+        ]
+    );
+    assert!(contacts.manifolds.iter().all(|m| !m.contacts.is_empty()));
+}
+
+#[test]
+fn collide_spawns_two_markers_at_contact_points() {
+    let mut app = headless_app(no_launcher());
+    update_until_playing(&mut app);
+    let (rock, ammo) = rock_and_ammo(&mut app);
+
+    app.world.send_event(uneven_collision(rock, ammo));
+    app.update();
+
+    // One unit out from either center, towards the other, and weighted by
+    // penetration: three quarters of the way up from the lower point.
+    let expected = [Vec3::new(-9.0, -99.5, 0.0), Vec3::new(9.0, -99.5, 0.0)];
+    let mut markers = app
+        .world
+        .query_filtered::<&Transform, (With<ImpactMarker>, Without<Parked>)>();
+    let points: Vec<Vec3> = markers.iter(&app.world).map(|t| t.translation).collect();
+    assert_eq!(points.len(), 2);
+    for expected in expected {
+        assert!(
+            points.iter().any(|point| point.abs_diff_eq(expected, 1e-4)),
+            "no marker at {expected:?}, markers at {points:?}"
+        );
+    }
+}
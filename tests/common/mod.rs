@@ -0,0 +1,126 @@
+use bevy::ecs::query::QueryFilter;
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+use bevy_xpbd_test::headless::HeadlessPlugins;
+use bevy_xpbd_test::layers::CollisionLayerName;
+use bevy_xpbd_test::projectile::ProjectilePlugin;
+use bevy_xpbd_test::ShootingRangePlugin;
+use bevy_xpbd_test::State;
+
+/// A headless app running the shooting range on the lone rock scenario, so
+/// that tests set up their own launchers, ready for its first update.
+#[allow(dead_code)]
+pub fn headless_app(mut plugin: ShootingRangePlugin) -> App {
+    plugin.scenario.path = "scenarios/rock.scenario.ron".into();
+    let mut app = App::new();
+    app.add_plugins((HeadlessPlugins, PhysicsPlugins::default(), plugin));
+    app
+}
+
+/// Finishes plugin setup, which [`App::run`] would otherwise do; the glTF
+/// loader is only registered at this point.
+pub fn finish(app: &mut App) {
+    app.finish();
+    app.cleanup();
+}
+
+/// Updates `app` until `done` returns true, panicking after `max_updates`.
+pub fn update_until(app: &mut App, max_updates: usize, mut done: impl FnMut(&mut App) -> bool) {
+    for _ in 0..max_updates {
+        app.update();
+        if done(app) {
+            return;
+        }
+    }
+    panic!("condition not met after {max_updates} updates");
+}
+
+/// Updates `app` until the loading state has finished.
+#[allow(dead_code)]
+pub fn update_until_playing(app: &mut App) {
+    finish(app);
+    // Assets are loaded on background threads, so this is bounded by wall time
+    // rather than simulated time.
+    update_until(app, 100_000, |app| state(app) == State::Play);
+}
+
+/// The current state of `app`.
+pub fn state(app: &App) -> State {
+    app.world
+        .resource::<bevy::prelude::State<State>>()
+        .get()
+        .clone()
+}
+
+/// How many entities match the query filter `F`.
+#[allow(dead_code)]
+pub fn count<F: QueryFilter>(app: &mut App) -> usize {
+    app.world.query_filtered::<(), F>().iter(&app.world).count()
+}
+
+/// The shooting range without a default launcher, for tests that fire on
+/// their own.
+#[allow(dead_code)]
+pub fn no_launcher() -> ShootingRangePlugin {
+    ShootingRangePlugin {
+        projectile: ProjectilePlugin {
+            default_launcher: None,
+            ..default()
+        },
+        ..default()
+    }
+}
+
+/// A rock at x = -10 on the `Object` layer and ammo at x = 10 on the `Ammo`
+/// layer, far below the range, once their colliders are set up.
+#[allow(dead_code)]
+pub fn rock_and_ammo(app: &mut App) -> (Entity, Entity) {
+    let rock = body(app, -10.0, "Object");
+    let ammo = body(app, 10.0, "Ammo");
+    update_until(app, 10, |app| {
+        app.world.get::<ColliderParent>(ammo).is_some()
+    });
+    (rock, ammo)
+}
+
+/// A static sphere on `layer`, far below the range.
+fn body(app: &mut App, x: f32, layer: &str) -> Entity {
+    app.world
+        .spawn((
+            RigidBody::Static,
+            Collider::sphere(1.0),
+            CollisionLayerName(layer.into()),
+            TransformBundle::from_transform(Transform::from_xyz(x, -100.0, 0.0)),
+        ))
+        .id()
+}
+
+/// `entity1` starting to touch `entity2` on its positive x side, one unit
+/// from either's center.
+#[allow(dead_code)]
+pub fn collision_started(entity1: Entity, entity2: Entity) -> Collision {
+    Collision(Contacts {
+        entity1,
+        entity2,
+        manifolds: vec![ContactManifold {
+            contacts: vec![ContactData {
+                point1: Vec3::X,
+                point2: Vec3::NEG_X,
+                normal1: Vec3::X,
+                normal2: Vec3::NEG_X,
+                penetration: 0.0,
+                normal_impulse: 0.0,
+                tangent_impulse: 0.0,
+                index: 0,
+            }],
+            normal1: Vec3::X,
+            normal2: Vec3::NEG_X,
+            index: 0,
+        }],
+        during_current_frame: true,
+        during_current_substep: true,
+        during_previous_frame: false,
+        total_normal_impulse: 0.0,
+        total_tangent_impulse: 0.0,
+    })
+}
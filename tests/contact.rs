@@ -0,0 +1,101 @@
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+use bevy_xpbd_test::contact::ContactSummary;
+
+fn contacts(manifolds: Vec<Vec<ContactData>>) -> Contacts {
+    Contacts {
+        entity1: Entity::from_raw(1),
+        entity2: Entity::from_raw(2),
+        manifolds: manifolds
+            .into_iter()
+            .enumerate()
+            .map(|(index, contacts)| ContactManifold {
+                normal1: contacts.first().map_or(Vec3::ZERO, |c| c.normal1),
+                normal2: contacts.first().map_or(Vec3::ZERO, |c| c.normal2),
+                contacts,
+                index,
+            })
+            .collect(),
+        during_current_frame: true,
+        during_current_substep: true,
+        during_previous_frame: false,
+        total_normal_impulse: 0.0,
+        total_tangent_impulse: 0.0,
+    }
+}
+
+fn contact(point: Vec3, normal: Vec3, penetration: f32) -> ContactData {
+    ContactData::new(point, -point, normal, -normal, penetration, 0)
+}
+
+#[test]
+fn empty_manifolds_have_no_summary() {
+    assert_eq!(ContactSummary::new(&contacts(vec![])), None);
+    assert_eq!(ContactSummary::new(&contacts(vec![vec![], vec![]])), None);
+}
+
+#[test]
+fn points_are_weighted_by_penetration() {
+    let summary = ContactSummary::new(&contacts(vec![
+        vec![contact(Vec3::ZERO, Vec3::Y, 0.3)],
+        vec![],
+        vec![
+            contact(Vec3::X * 4.0, Vec3::Y, 0.1),
+            // Separated points don't pull the impact towards them.
+            contact(Vec3::Z * 9.0, Vec3::Y, -0.5),
+        ],
+    ]))
+    .unwrap();
+
+    assert!(summary.point1.abs_diff_eq(Vec3::X, 1e-6));
+    assert!(summary.point2.abs_diff_eq(-Vec3::X, 1e-6));
+    assert_eq!(summary.max_penetration, 0.3);
+    assert_eq!(summary.count, 3);
+}
+
+#[test]
+fn points_count_evenly_without_penetration() {
+    let summary = ContactSummary::new(&contacts(vec![vec![
+        contact(Vec3::ZERO, Vec3::Y, 0.0),
+        contact(Vec3::X * 2.0, Vec3::Y, 0.0),
+        contact(Vec3::Y * 3.0, Vec3::Y, -0.1),
+    ]]))
+    .unwrap();
+
+    assert!(summary
+        .point1
+        .abs_diff_eq(Vec3::new(2.0, 3.0, 0.0) / 3.0, 1e-6));
+    assert_eq!(summary.max_penetration, 0.0);
+}
+
+#[test]
+fn normals_are_averaged() {
+    let summary = ContactSummary::new(&contacts(vec![
+        vec![contact(Vec3::ZERO, Vec3::X, 0.1)],
+        vec![contact(Vec3::ZERO, Vec3::Y, 0.1)],
+    ]))
+    .unwrap();
+    let expected = Vec3::new(1.0, 1.0, 0.0).normalize();
+    assert!(summary.normal1.abs_diff_eq(expected, 1e-6));
+    assert!(summary.normal2.abs_diff_eq(-expected, 1e-6));
+
+    let opposed = ContactSummary::new(&contacts(vec![vec![
+        contact(Vec3::ZERO, Vec3::X, 0.1),
+        contact(Vec3::ZERO, Vec3::NEG_X, 0.1),
+    ]]))
+    .unwrap();
+    assert_eq!(opposed.normal1, Vec3::ZERO);
+}
+
+#[test]
+fn world_points_follow_the_colliders() {
+    let summary =
+        ContactSummary::new(&contacts(vec![vec![contact(Vec3::X, Vec3::Y, 0.1)]])).unwrap();
+    let t1 = GlobalTransform::from(
+        Transform::from_xyz(0.0, 5.0, 0.0).with_rotation(Quat::from_rotation_z(90f32.to_radians())),
+    );
+    let t2 = GlobalTransform::from_xyz(1.0, 0.0, 0.0);
+    let (p1, p2) = summary.world_points(&t1, &t2);
+    assert!(p1.abs_diff_eq(Vec3::new(0.0, 6.0, 0.0), 1e-6));
+    assert!(p2.abs_diff_eq(Vec3::ZERO, 1e-6));
+}
@@ -0,0 +1,42 @@
+use bevy::gltf::GltfExtras;
+use bevy_xpbd_test::collidable::*;
+use bevy_xpbd_test::extras::NodeExtras;
+
+fn parse(value: &str) -> NodeExtras {
+    NodeExtras::parse(&GltfExtras {
+        value: value.to_string(),
+    })
+    .unwrap()
+}
+
+#[test]
+fn parses_blender_custom_properties() {
+    let extras = parse(r#"{"collider":"convex","layer":"Object","hidden":true,"fracture":6}"#);
+    assert_eq!(extras.layer, Some("Object".into()));
+    assert!(extras.hidden);
+    assert_eq!(extras.fracture, Some(6));
+    assert_eq!(extras.strategy(), Some(ColliderStrategy::ConvexHull));
+}
+
+#[test]
+fn parses_collider_material() {
+    let extras = parse(r#"{"density":2.5,"restitution":0.6,"friction":0.4}"#);
+    assert_eq!(extras.density, Some(2.5));
+    assert_eq!(extras.restitution, Some(0.6));
+    assert_eq!(extras.friction, Some(0.4));
+}
+
+#[test]
+fn missing_properties_use_defaults() {
+    let extras = parse(r#"{"layer":"Ammo","unrelated":1}"#);
+    assert_eq!(extras.layer, Some("Ammo".into()));
+    assert!(!extras.hidden);
+    assert_eq!(extras.fracture, None);
+    assert_eq!(extras.restitution, None);
+    assert_eq!(extras.strategy(), Some(ColliderStrategy::Trimesh));
+}
+
+#[test]
+fn unknown_collider_has_no_strategy() {
+    assert_eq!(parse(r#"{"collider":"blob"}"#).strategy(), None);
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use bevy::ecs::event::ManualEventReader;
+use bevy::prelude::*;
+use bevy::render::mesh::VertexAttributeValues;
+use bevy_xpbd_3d::prelude::*;
+use bevy_xpbd_test::fracture::*;
+use bevy_xpbd_test::health::Destroyed;
+use bevy_xpbd_test::health::Health;
+use bevy_xpbd_test::layers::CollisionMatrix;
+use bevy_xpbd_test::projectile::Launcher;
+use bevy_xpbd_test::projectile::ProjectilePlugin;
+use bevy_xpbd_test::ShootingRangePlugin;
+use common::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn fragments_stay_within_the_fractured_shape() {
+    let sphere = Sphere::new(2.0).mesh().uv(16, 8);
+    let Some(VertexAttributeValues::Float32x3(points)) = sphere.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        panic!("sphere without positions");
+    };
+    let points: Vec<Vec3> = points.iter().map(|&p| Vec3::from(p) + Vec3::X).collect();
+
+    let fragments = fracture(&points, 6, &mut StdRng::seed_from_u64(7));
+    assert!((2..=6).contains(&fragments.len()), "{}", fragments.len());
+    for fragment in fragments {
+        let Some(VertexAttributeValues::Float32x3(vertices)) =
+            fragment.mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("fragment without positions");
+        };
+        assert!(vertices.len() >= 12);
+        for &v in vertices {
+            let distance = (fragment.center + Vec3::from(v)).distance(Vec3::X);
+            assert!(distance <= 2.0 + 1e-4, "{distance}");
+        }
+    }
+}
+
+#[test]
+fn destroyed_rock_breaks_into_debris() {
+    let mut app = headless_app(ShootingRangePlugin {
+        projectile: ProjectilePlugin {
+            default_launcher: Some(Launcher {
+                fire_rate: 4.0,
+                ..default()
+            }),
+            ..default()
+        },
+        fracture: FracturePlugin {
+            lifetime: 0.5,
+            ..default()
+        },
+        ..default()
+    });
+    update_until_playing(&mut app);
+    // Only the rock has health, and a single hit destroys it.
+    *app.world.query::<&mut Health>().single_mut(&mut app.world) = Health::new(1.0);
+
+    let mut reader = ManualEventReader::<Destroyed>::default();
+    update_until(&mut app, 600, |app| {
+        let events = app.world.resource::<Events<Destroyed>>();
+        reader.read(events).next().is_some()
+    });
+    // Colliders are built from the debris meshes once they are spawned.
+    app.update();
+
+    let debris_layers = app.world.resource::<CollisionMatrix>().layers("Debris");
+    let mut debris = app
+        .world
+        .query_filtered::<(&RigidBody, &Collider, &CollisionLayers), With<Debris>>();
+    let pieces: Vec<_> = debris.iter(&app.world).collect();
+    assert!((2..=11).contains(&pieces.len()), "{}", pieces.len());
+    for (body, _, layers) in pieces {
+        assert_eq!(*body, RigidBody::Dynamic);
+        assert_eq!(Some(*layers), debris_layers);
+    }
+
+    update_until(&mut app, 60, |app| count::<With<Debris>>(app) == 0);
+}
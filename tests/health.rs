@@ -0,0 +1,79 @@
+mod common;
+
+use bevy::ecs::event::ManualEventReader;
+use bevy::prelude::*;
+use bevy_xpbd_test::health::*;
+use bevy_xpbd_test::projectile::Launcher;
+use bevy_xpbd_test::projectile::ProjectilePlugin;
+use bevy_xpbd_test::ShootingRangePlugin;
+use common::*;
+
+#[test]
+fn damage_follows_kinetic_energy() {
+    let velocity = Vec3::new(3.0, 0.0, 4.0);
+    // 0.5 * 2 * 5² joules.
+    assert_eq!(impact_damage(2.0, velocity, Damage(1.0), Armor(0.0)), 25.0);
+    assert_eq!(impact_damage(2.0, velocity, Damage(0.1), Armor(0.0)), 2.5);
+    assert_eq!(impact_damage(8.0, velocity, Damage(0.1), Armor(0.0)), 10.0);
+    assert_eq!(
+        impact_damage(2.0, velocity * 2.0, Damage(0.1), Armor(0.0)),
+        10.0
+    );
+}
+
+#[test]
+fn armor_absorbs_damage_per_hit() {
+    let velocity = Vec3::new(3.0, 0.0, 4.0);
+    assert_eq!(impact_damage(2.0, velocity, Damage(1.0), Armor(5.0)), 20.0);
+    assert_eq!(impact_damage(2.0, velocity, Damage(1.0), Armor(30.0)), 0.0);
+}
+
+#[test]
+fn rock_is_despawned_after_enough_hits() {
+    let mut app = headless_app(ShootingRangePlugin {
+        projectile: ProjectilePlugin {
+            default_launcher: Some(Launcher {
+                fire_rate: 4.0,
+                ..default()
+            }),
+            ..default()
+        },
+        ..default()
+    });
+    update_until_playing(&mut app);
+    // Only the rock has health.
+    let rock = app
+        .world
+        .query_filtered::<Entity, With<Health>>()
+        .single(&app.world);
+    *app.world.get_mut::<Health>(rock).unwrap() = Health::new(100.0);
+    let descendants = |app: &App| {
+        let mut found = vec![rock];
+        let mut i = 0;
+        while let Some(&e) = found.get(i) {
+            found.extend(app.world.get::<Children>(e).into_iter().flatten());
+            i += 1;
+        }
+        found
+    };
+    update_until(&mut app, 100, |app| descendants(app).len() > 1);
+    let scene = descendants(&app);
+
+    let mut reader = ManualEventReader::<Destroyed>::default();
+    let mut hits = Vec::new();
+    update_until(&mut app, 2_000, |app| {
+        if let Some(health) = app.world.get::<Health>(rock) {
+            hits.push(health.current);
+        }
+        let events = app.world.resource::<Events<Destroyed>>();
+        reader.read(events).any(|d| d.entity == rock)
+    });
+
+    hits.dedup();
+    assert!(hits.len() > 2, "destroyed by the first hits: {hits:?}");
+    assert!(hits.windows(2).all(|w| w[1] < w[0]));
+    app.update();
+    for entity in scene {
+        assert!(app.world.get_entity(entity).is_none(), "{entity:?} left");
+    }
+}
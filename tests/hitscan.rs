@@ -0,0 +1,114 @@
+mod common;
+
+use bevy::ecs::event::ManualEventReader;
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+use bevy_xpbd_test::health::Damage;
+use bevy_xpbd_test::health::Health;
+use bevy_xpbd_test::hitscan::Hitscan;
+use bevy_xpbd_test::hitscan::Tracer;
+use bevy_xpbd_test::impact::ImpactEvent;
+use bevy_xpbd_test::marker::ImpactMarker;
+use bevy_xpbd_test::pool::Parked;
+use bevy_xpbd_test::projectile::Launcher;
+use bevy_xpbd_test::projectile::Projectile;
+use bevy_xpbd_test::Stats;
+use common::*;
+
+/// A range without projectile launchers whose rock has its colliders, and
+/// the rock.
+fn range() -> (App, Entity) {
+    let mut app = headless_app(no_launcher());
+    update_until_playing(&mut app);
+    let rock = app
+        .world
+        .query_filtered::<Entity, With<Health>>()
+        .single(&app.world);
+    update_until(&mut app, 100, |app| {
+        app.world
+            .query::<&ColliderParent>()
+            .iter(&app.world)
+            .any(|parent| parent.get() == rock)
+    });
+    (app, rock)
+}
+
+fn hitscan_launcher(app: &mut App, velocity: Vec3) -> Entity {
+    app.world
+        .spawn((
+            SpatialBundle::default(),
+            Launcher {
+                fire_rate: 10.0,
+                velocity,
+                ..default()
+            },
+            Hitscan::default(),
+        ))
+        .id()
+}
+
+#[test]
+fn rays_hit_the_rock_instantly() {
+    let (mut app, rock) = range();
+    // The default launcher velocity aims at the rock.
+    let launcher = hitscan_launcher(&mut app, Launcher::default().velocity);
+
+    let mut reader = ManualEventReader::<ImpactEvent>::default();
+    let mut impacts = Vec::new();
+    update_until(&mut app, 100, |app| {
+        let events = app.world.resource::<Events<ImpactEvent>>();
+        impacts.extend(reader.read(events).cloned());
+        app.world.resource::<Stats>().shots_fired >= 3
+    });
+
+    assert_eq!(impacts.len(), 3);
+    let center = app.world.get::<Transform>(rock).unwrap().translation;
+    for impact in &impacts {
+        assert_eq!((impact.projectile, impact.target), (launcher, rock));
+        assert_eq!(impact.point, impact.projectile_point);
+        assert_eq!(impact.damage, Some(Launcher::default().damage));
+        assert_eq!(impact.projectile_mass, 1.0);
+        // On the near side of the rock, facing the launcher.
+        assert!(impact.point.length() < center.length(), "{impact:?}");
+        assert!(impact.normal.dot(impact.point) < 0.0, "{impact:?}");
+        assert_eq!(
+            impact.layer_pair,
+            (Some("Ammo".to_string()), Some("Object".to_string()))
+        );
+    }
+    assert_eq!(app.world.resource::<Stats>().collisions, 3);
+
+    // No projectiles were fired, but the hits left markers and tracers and
+    // wore the rock down.
+    assert_eq!(count::<(With<Projectile>, Without<Parked>)>(&mut app), 0);
+    app.update();
+    assert!(count::<(With<ImpactMarker>, Without<Parked>)>(&mut app) > 0);
+    let tracers: Vec<Tracer> = app
+        .world
+        .query::<&Tracer>()
+        .iter(&app.world)
+        .copied()
+        .collect();
+    assert!(!tracers.is_empty());
+    assert!(tracers
+        .iter()
+        .all(|t| impacts.iter().any(|i| i.point == t.end)));
+    let health = app.world.get::<Health>(rock).unwrap();
+    assert!(health.current < health.max);
+    assert!(app.world.get::<Damage>(launcher).is_none());
+}
+
+#[test]
+fn rays_that_miss_leave_a_tracer_of_full_range() {
+    let (mut app, _) = range();
+    let velocity = Vec3::new(-10.0, 0.0, 0.0);
+    hitscan_launcher(&mut app, velocity);
+    update_until(&mut app, 100, |app| {
+        app.world.resource::<Stats>().shots_fired > 0
+    });
+
+    assert_eq!(app.world.resource::<Stats>().collisions, 0);
+    let tracer = *app.world.query::<&Tracer>().single(&app.world);
+    assert_eq!(tracer.start, Vec3::ZERO);
+    assert_eq!(tracer.end, Vec3::new(-Hitscan::default().range, 0.0, 0.0));
+}
@@ -0,0 +1,129 @@
+mod common;
+
+use bevy::ecs::event::ManualEventReader;
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+use bevy_xpbd_test::impact::ImpactEvent;
+use bevy_xpbd_test::impact::ImpactPhase;
+use bevy_xpbd_test::impact::ImpactPlugin;
+use bevy_xpbd_test::projectile::Projectile;
+use bevy_xpbd_test::session::RunCommand;
+use bevy_xpbd_test::ShootingRangePlugin;
+use bevy_xpbd_test::State;
+use common::*;
+
+/// A range without launchers, with a rock at x = -10 and a projectile at
+/// x = 10 flying towards it.
+fn range(impacts: ImpactPlugin) -> (App, Entity, Entity) {
+    let mut app = headless_app(ShootingRangePlugin {
+        impacts,
+        ..no_launcher()
+    });
+    update_until_playing(&mut app);
+
+    let (rock, ammo) = rock_and_ammo(&mut app);
+    app.world.entity_mut(ammo).insert((
+        RigidBody::Kinematic,
+        LinearVelocity(Vec3::NEG_X),
+        Projectile,
+    ));
+    (app, rock, ammo)
+}
+
+fn impacts(app: &App, reader: &mut ManualEventReader<ImpactEvent>) -> Vec<ImpactEvent> {
+    let events = app.world.resource::<Events<ImpactEvent>>();
+    reader.read(events).cloned().collect()
+}
+
+#[test]
+fn impacts_are_seen_from_the_projectile() {
+    let (mut app, rock, ammo) = range(default());
+    let mut reader = ManualEventReader::default();
+    // The rock is the first entity, so the impact has to be turned around.
+    let rock_x = app.world.get::<Transform>(rock).unwrap().translation.x;
+    let ammo_x = app.world.get::<Transform>(ammo).unwrap().translation.x;
+    app.world.send_event(collision_started(rock, ammo));
+    app.update();
+
+    let mass = app.world.get::<Mass>(ammo).unwrap().0;
+    let impacts = impacts(&app, &mut reader);
+    assert_eq!(impacts.len(), 1);
+    let impact = &impacts[0];
+    assert_eq!(impact.phase, ImpactPhase::Started);
+    assert_eq!((impact.projectile, impact.target), (ammo, rock));
+    assert!(impact
+        .point
+        .abs_diff_eq(Vec3::new(rock_x + 1.0, -100.0, 0.0), 1e-4));
+    assert!(impact
+        .projectile_point
+        .abs_diff_eq(Vec3::new(ammo_x - 1.0, -100.0, 0.0), 1e-4));
+    assert!(impact.normal.abs_diff_eq(Vec3::X, 1e-6));
+    assert_eq!(impact.relative_velocity, Vec3::NEG_X);
+    assert!((impact.impulse_estimate - mass).abs() < 1e-4);
+    assert_eq!(
+        impact.layer_pair,
+        (Some("Ammo".to_string()), Some("Object".to_string()))
+    );
+}
+
+#[test]
+fn sustained_and_ended_phases_are_opt_in() {
+    let sustained = |app: &mut App, rock, ammo| {
+        let Collision(mut contacts) = collision_started(rock, ammo);
+        contacts.during_previous_frame = true;
+        app.world.send_event(Collision(contacts));
+    };
+    let phases = |app: &mut App, rock, ammo| {
+        let mut reader = ManualEventReader::default();
+        let mut phases = Vec::new();
+        let mut update = |app: &mut App| {
+            app.update();
+            phases.extend(impacts(app, &mut reader).iter().map(|i| i.phase));
+        };
+        app.world.send_event(collision_started(rock, ammo));
+        update(app);
+        sustained(app, rock, ammo);
+        update(app);
+        app.world.send_event(CollisionEnded(rock, ammo));
+        update(app);
+        phases
+    };
+
+    let (mut app, rock, ammo) = range(default());
+    assert_eq!(phases(&mut app, rock, ammo), [ImpactPhase::Started]);
+
+    let (mut app, rock, ammo) = range(ImpactPlugin {
+        sustained: true,
+        ended: true,
+    });
+    assert_eq!(
+        phases(&mut app, rock, ammo),
+        [
+            ImpactPhase::Started,
+            ImpactPhase::Sustained,
+            ImpactPhase::Ended
+        ]
+    );
+}
+
+#[test]
+fn contacts_left_over_from_a_run_never_end_in_the_next() {
+    let (mut app, rock, ammo) = range(ImpactPlugin {
+        sustained: false,
+        ended: true,
+    });
+    let mut reader = ManualEventReader::default();
+    app.world.send_event(collision_started(rock, ammo));
+    app.update();
+    assert_eq!(impacts(&app, &mut reader).len(), 1);
+
+    app.world.send_event(RunCommand::Menu);
+    update_until(&mut app, 10, |app| state(app) == State::Menu);
+    app.world.send_event(RunCommand::Start);
+    update_until(&mut app, 10, |app| state(app) == State::Play);
+    impacts(&app, &mut reader);
+
+    app.world.send_event(CollisionEnded(rock, ammo));
+    app.update();
+    assert!(impacts(&app, &mut reader).is_empty());
+}
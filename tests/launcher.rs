@@ -0,0 +1,117 @@
+mod common;
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+use bevy_xpbd_test::despawn::DelayedDespawn;
+use bevy_xpbd_test::pool::Parked;
+use bevy_xpbd_test::projectile::FiredAt;
+use bevy_xpbd_test::projectile::Launcher;
+use bevy_xpbd_test::ShootingRangePlugin;
+use bevy_xpbd_test::Stats;
+use common::*;
+
+#[test]
+fn launchers_fire_bursts_within_their_spread() {
+    let mut app = headless_app(no_launcher());
+    update_until_playing(&mut app);
+
+    let spread = 0.2;
+    let velocity = Vec3::new(0.0, 0.0, -20.0);
+    for x in [-50.0, 50.0] {
+        app.world.spawn((
+            SpatialBundle::from_transform(Transform::from_xyz(x, 0.0, 0.0)),
+            Launcher {
+                fire_rate: 10.0,
+                muzzle_offset: Vec3::Y,
+                velocity,
+                spread,
+                burst: 3,
+                ..default()
+            },
+        ));
+    }
+    update_until(&mut app, 60, |app| {
+        app.world.resource::<Stats>().shots_fired > 0
+    });
+
+    assert_eq!(app.world.resource::<Stats>().shots_fired, 6);
+    let mut projectiles = app
+        .world
+        .query_filtered::<(&LinearVelocity, &Transform), With<DelayedDespawn>>();
+    let projectiles: Vec<_> = projectiles.iter(&app.world).collect();
+    assert_eq!(projectiles.len(), 6);
+    for (LinearVelocity(v), transform) in projectiles {
+        assert!((v.length() - velocity.length()).abs() < 1e-3);
+        assert!(v.angle_between(velocity) <= spread + 1e-4);
+        // Projectiles leave the muzzle, one unit above either launcher, and
+        // have moved for at most one physics step since.
+        let muzzle = Vec3::new(transform.translation.x.signum() * 50.0, 1.0, 0.0);
+        let moved = transform.translation - muzzle;
+        assert!(
+            moved.length() <= velocity.length() / 60.0 + 1e-3,
+            "{moved:?}"
+        );
+    }
+}
+
+#[test]
+fn launchers_catch_up_on_bursts_missed_within_a_frame() {
+    let mut launcher = Launcher {
+        fire_rate: 4.0,
+        ..default()
+    };
+    // Three intervals in one frame, as after a hitch.
+    let bursts = launcher.trigger(Duration::from_millis(750), &GlobalTransform::IDENTITY, []);
+    assert_eq!(bursts, vec![launcher.velocity; 3]);
+
+    launcher.auto_fire = false;
+    let bursts = launcher.trigger(Duration::from_millis(750), &GlobalTransform::IDENTITY, []);
+    assert!(bursts.is_empty());
+}
+
+#[test]
+fn projectiles_record_when_they_were_fired() {
+    let mut app = headless_app(ShootingRangePlugin::default());
+    update_until_playing(&mut app);
+    // Fired first, but with the longer lifetime.
+    app.world.spawn((
+        SpatialBundle::default(),
+        Launcher {
+            fire_rate: 4.0,
+            lifetime: 10.0,
+            ..default()
+        },
+    ));
+    update_until(&mut app, 60, |app| {
+        app.world.resource::<Stats>().shots_fired > 0
+    });
+    let elapsed = app.world.resource::<Time>().elapsed_seconds();
+    let mut launchers = app.world.query::<&mut Launcher>();
+    for mut launcher in launchers.iter_mut(&mut app.world) {
+        launcher.lifetime = 1.0;
+    }
+    update_until(&mut app, 60, |app| {
+        app.world.resource::<Stats>().shots_fired > 1
+    });
+    for _ in 0..3 {
+        app.update();
+    }
+
+    let mut fired = app
+        .world
+        .query_filtered::<(&FiredAt, &DelayedDespawn), Without<Parked>>();
+    let mut fired: Vec<_> = fired
+        .iter(&app.world)
+        .map(|(at, despawn)| (at.0, despawn.fraction_remaining()))
+        .collect();
+    fired.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let [(first, first_left), (second, second_left)] = fired[..] else {
+        panic!("{fired:?}");
+    };
+    assert_eq!(first, elapsed);
+    assert!(second > first);
+    // The order a camera following the latest shot can't get from lifetimes.
+    assert!(first_left > second_left, "{fired:?}");
+}
@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+use bevy_xpbd_test::layers::*;
+
+fn shipped_matrix() -> CollisionMatrix {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/collision.layers.ron");
+    ron::de::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn pairs_collide_both_ways() {
+    let matrix = shipped_matrix();
+    let layers = |name| matrix.layers(name).unwrap();
+    assert!(layers("Ammo").interacts_with(layers("Object")));
+    assert!(layers("Object").interacts_with(layers("Ammo")));
+    assert!(layers("Debris").interacts_with(layers("Terrain")));
+    assert!(!layers("Ammo").interacts_with(layers("Ammo")));
+    assert!(!layers("Sensor").interacts_with(layers("Ammo")));
+    assert_eq!(matrix.layers("Missing"), None);
+}
+
+#[test]
+fn changing_the_matrix_reapplies_layers() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        CollisionMatrixPlugin,
+    ));
+    let entity = app
+        .world
+        .spawn((
+            CollisionLayerName("Ammo".into()),
+            CollisionMatrix::default().layers("Ammo").unwrap(),
+        ))
+        .id();
+    app.update();
+
+    *app.world.resource_mut::<CollisionMatrix>() = shipped_matrix();
+    app.update();
+
+    assert_eq!(
+        app.world.get::<CollisionLayers>(entity),
+        shipped_matrix().layers("Ammo").as_ref()
+    );
+    assert_ne!(
+        shipped_matrix().layers("Ammo"),
+        CollisionMatrix::default().layers("Ammo")
+    );
+}
@@ -0,0 +1,127 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+use bevy_xpbd_test::headless::HeadlessPlugins;
+use bevy_xpbd_test::loading::LoadStatus;
+use bevy_xpbd_test::loading::LoadingProgress;
+use bevy_xpbd_test::scenario::ScenarioPlugin;
+use bevy_xpbd_test::ShootingRangePlugin;
+use bevy_xpbd_test::State;
+use common::*;
+
+/// The range on the scenario at `path`, instead of the one [`headless_app`]
+/// picks.
+fn range(path: &str) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        HeadlessPlugins,
+        PhysicsPlugins::default(),
+        ShootingRangePlugin {
+            scenario: ScenarioPlugin { path: path.into() },
+            ..default()
+        },
+    ));
+    app
+}
+
+/// Updates `app` until loading is over, one way or the other.
+fn load(app: &mut App) -> State {
+    finish(app);
+    update_until(app, 100_000, |app| {
+        !matches!(state(app), State::Load | State::Menu)
+    });
+    state(app)
+}
+
+fn errors(app: &App) -> &[String] {
+    &app.world.resource::<LoadingProgress>().errors
+}
+
+#[test]
+fn progress_covers_every_asset() {
+    let mut app = headless_app(ShootingRangePlugin::default());
+    assert_eq!(load(&mut app), State::Play);
+
+    let progress = app.world.resource::<LoadingProgress>();
+    let paths: Vec<&str> = progress.assets.iter().map(|a| a.path.as_str()).collect();
+    for path in [
+        "rock.glb",
+        "rock.glb#Mesh1/Primitive0",
+        "collision.layers.ron",
+        "scenarios",
+        "scenarios/rock.scenario.ron",
+        // Only named by the scenario.
+        "rock.glb#Scene0",
+    ] {
+        assert!(paths.contains(&path), "{path} not in {paths:?}");
+    }
+    assert!(progress
+        .assets
+        .iter()
+        .all(|asset| asset.status == LoadStatus::Loaded));
+    assert_eq!(progress.loaded(), progress.assets.len());
+    assert!(!progress.failed());
+}
+
+#[test]
+fn missing_sub_asset_fails_loading() {
+    let mut app = range("tests/missing_label.scenario.ron");
+    assert_eq!(load(&mut app), State::LoadFailed);
+    assert_eq!(errors(&app), ["rock.glb has no sub-asset named `Scene9`"]);
+    let progress = app.world.resource::<LoadingProgress>();
+    let scene = progress
+        .assets
+        .iter()
+        .find(|asset| asset.path == "rock.glb#Scene9")
+        .unwrap();
+    assert_eq!(scene.status, LoadStatus::Failed);
+}
+
+#[test]
+fn missing_file_fails_loading() {
+    let mut app = range("tests/missing_file.scenario.ron");
+    assert_eq!(load(&mut app), State::LoadFailed);
+    assert!(
+        errors(&app)
+            .iter()
+            .any(|error| error.contains("boulder.glb")),
+        "{:?}",
+        errors(&app)
+    );
+}
+
+#[test]
+fn missing_scenario_fails_loading() {
+    let mut app = range("scenarios/nowhere.scenario.ron");
+    assert_eq!(load(&mut app), State::LoadFailed);
+    assert!(
+        errors(&app)
+            .iter()
+            .any(|error| error.contains("scenarios/nowhere.scenario.ron")),
+        "{:?}",
+        errors(&app)
+    );
+}
+
+#[test]
+fn invalid_scenarios_fail_loading() {
+    for (path, error) in [
+        (
+            "tests/unknown_collider.scenario.ron",
+            r#"target 0 has unknown collider "cylinder""#,
+        ),
+        (
+            "tests/unknown_layer.scenario.ron",
+            r#"launcher 0 casts rays on unknown layer "Laser""#,
+        ),
+    ] {
+        let mut app = range(path);
+        assert_eq!(load(&mut app), State::LoadFailed, "{path}");
+        assert!(
+            errors(&app).iter().any(|e| e.contains(error)),
+            "{path}: {:?}",
+            errors(&app)
+        );
+    }
+}
@@ -0,0 +1,43 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_xpbd_test::marker::ImpactFxAssets;
+use bevy_xpbd_test::marker::ImpactMarker;
+use bevy_xpbd_test::marker::ImpactMarkerPlugin;
+use bevy_xpbd_test::pool::Parked;
+use bevy_xpbd_test::ShootingRangePlugin;
+use bevy_xpbd_test::Stats;
+use common::*;
+
+#[test]
+fn impacts_share_materials() {
+    let mut app = headless_app(ShootingRangePlugin {
+        impact_markers: ImpactMarkerPlugin {
+            pair_colors: vec![("Ammo".into(), "Object".into(), Color::RED)],
+            ..default()
+        },
+        ..no_launcher()
+    });
+    update_until_playing(&mut app);
+
+    let (object, ammo) = rock_and_ammo(&mut app);
+    let materials = app.world.resource::<Assets<StandardMaterial>>().len();
+
+    for _ in 0..20 {
+        app.world
+            .send_event_batch((0..100).map(|_| collision_started(object, ammo)));
+        app.update();
+    }
+
+    assert_eq!(app.world.resource::<Stats>().collisions, 2000);
+    assert_eq!(
+        app.world.resource::<Assets<StandardMaterial>>().len(),
+        materials
+    );
+    let pair_material = app.world.resource::<ImpactFxAssets>().pairs[0].2.clone();
+    let mut markers = app
+        .world
+        .query_filtered::<&Handle<StandardMaterial>, (With<ImpactMarker>, Without<Parked>)>();
+    assert_eq!(markers.iter(&app.world).count(), 4000);
+    assert!(markers.iter(&app.world).all(|m| *m == pair_material));
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+use bevy_xpbd_test::health::Health;
+use bevy_xpbd_test::marker::ImpactMarker;
+use bevy_xpbd_test::marker::ImpactMarkerPlugin;
+use bevy_xpbd_test::pool::Parked;
+use bevy_xpbd_test::pool::PARKING_SPOT;
+use bevy_xpbd_test::projectile::Launcher;
+use bevy_xpbd_test::projectile::Projectile;
+use bevy_xpbd_test::projectile::ProjectilePlugin;
+use bevy_xpbd_test::ShootingRangePlugin;
+use bevy_xpbd_test::Stats;
+use common::*;
+
+#[test]
+fn projectiles_and_markers_are_reused() {
+    // About eight projectiles and eight markers are alive at any time.
+    let mut app = headless_app(ShootingRangePlugin {
+        projectile: ProjectilePlugin {
+            default_launcher: Some(Launcher {
+                fire_rate: 4.0,
+                lifetime: 2.0,
+                ..default()
+            }),
+            pool_size: 12,
+        },
+        impact_markers: ImpactMarkerPlugin {
+            pool_size: 24,
+            ..default()
+        },
+        ..default()
+    });
+    update_until_playing(&mut app);
+    // Destroying the rock would end the run before the pools wrap around.
+    *app.world.query::<&mut Health>().single_mut(&mut app.world) = Health::new(f32::MAX);
+
+    update_until(&mut app, 1_000, |app| {
+        app.world.resource::<Stats>().shots_fired >= 30
+    });
+
+    // Reused projectiles were re-enabled and kept hitting the rock.
+    assert!(app.world.resource::<Stats>().collisions > 12);
+    assert_eq!(count::<With<Projectile>>(&mut app), 12);
+    assert_eq!(count::<With<ImpactMarker>>(&mut app), 24);
+
+    let mut parked = app
+        .world
+        .query_filtered::<(&Transform, &Visibility), With<Parked>>();
+    assert!(parked.iter(&app.world).count() > 0);
+    for (transform, visibility) in parked.iter(&app.world) {
+        assert_eq!(transform.translation, PARKING_SPOT);
+        assert_eq!(visibility, Visibility::Hidden);
+    }
+
+    let mut colliders = app.world.query::<(Entity, &CollisionLayers)>();
+    let mut parked_colliders = 0;
+    for (entity, layers) in colliders.iter(&app.world) {
+        if is_parked(&app.world, entity) {
+            assert_eq!(*layers, CollisionLayers::NONE);
+            parked_colliders += 1;
+        }
+    }
+    assert!(parked_colliders > 0);
+}
+
+fn is_parked(world: &World, mut entity: Entity) -> bool {
+    loop {
+        if world.get::<Parked>(entity).is_some() {
+            return true;
+        }
+        match world.get::<Parent>(entity) {
+            Some(parent) => entity = parent.get(),
+            None => return false,
+        }
+    }
+}
@@ -0,0 +1,123 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+use bevy_xpbd_test::headless::HeadlessPlugins;
+use bevy_xpbd_test::health::Health;
+use bevy_xpbd_test::layers::CollisionLayerName;
+use bevy_xpbd_test::projectile::Launcher;
+use bevy_xpbd_test::scenario::Scenario;
+use bevy_xpbd_test::scenario::ScenarioAssets;
+use bevy_xpbd_test::scenario::SelectedScenario;
+use bevy_xpbd_test::scenario::TargetSpec;
+use bevy_xpbd_test::session::RunCommand;
+use bevy_xpbd_test::ShootingRangePlugin;
+use bevy_xpbd_test::State;
+use common::*;
+
+/// The range on its default scenario, which [`headless_app`] replaces.
+fn default_range() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        HeadlessPlugins,
+        PhysicsPlugins::default(),
+        ShootingRangePlugin::default(),
+    ));
+    update_until_playing(&mut app);
+    app
+}
+
+fn rock(app: &mut App) -> Entity {
+    app.world
+        .query_filtered::<Entity, With<Health>>()
+        .single(&app.world)
+}
+
+#[test]
+fn default_scenario_parses() {
+    let source = std::fs::read_to_string("assets/scenarios/default.scenario.ron").unwrap();
+    let scenario: Scenario = ron::from_str(&source).unwrap();
+    assert_eq!(scenario.targets.len(), 1);
+    assert_eq!(scenario.targets[0].scene, "rock.glb#Scene0");
+    assert_eq!(scenario.launchers.len(), 1);
+    assert_eq!(scenario.lights.len(), 1);
+    assert!(scenario.camera.is_some());
+}
+
+#[test]
+fn targets_can_be_static() {
+    let target: TargetSpec =
+        ron::from_str(r#"(scene: "rock.glb#Scene0", body: (body_type: Static))"#).unwrap();
+    assert_eq!(target.body.rigid_body(), RigidBody::Static);
+}
+
+#[test]
+fn default_scenario_builds_the_range() {
+    let mut app = default_range();
+    let rock = rock(&mut app);
+    let transform = app.world.get::<Transform>(rock).unwrap();
+    assert_eq!(transform.translation, Vec3::new(15.0, 15.0, 0.0));
+    assert_eq!(transform.scale, Vec3::splat(4.0));
+    assert_eq!(app.world.get::<Health>(rock).unwrap().max, 200.0);
+    assert_eq!(count::<With<Launcher>>(&mut app), 1);
+    assert_eq!(count::<With<DirectionalLight>>(&mut app), 1);
+}
+
+#[test]
+fn modified_scenario_rebuilds_the_range() {
+    let mut app = default_range();
+    let old_rock = rock(&mut app);
+
+    let handle = app.world.resource::<ScenarioAssets>().scenario.clone();
+    let mut scenarios = app.world.resource_mut::<Assets<Scenario>>();
+    let target = &mut scenarios.get_mut(&handle).unwrap().targets[0];
+    target.placement.translation = Vec3::new(-20.0, 5.0, 0.0);
+    target.colliders.layer = Some("Terrain".into());
+
+    // Replaced rather than duplicated.
+    update_until(&mut app, 10, |app| app.world.get_entity(old_rock).is_none());
+    let rock = rock(&mut app);
+    let translation = app.world.get::<Transform>(rock).unwrap().translation;
+    assert_eq!(translation, Vec3::new(-20.0, 5.0, 0.0));
+    assert_eq!(count::<With<Launcher>>(&mut app), 1);
+    assert_eq!(count::<With<DirectionalLight>>(&mut app), 1);
+
+    update_until(&mut app, 100, |app| {
+        app.world
+            .query::<(&ColliderParent, &CollisionLayerName)>()
+            .iter(&app.world)
+            .any(|(parent, layer)| parent.get() == rock && layer.0 == "Terrain")
+    });
+}
+
+#[test]
+fn picked_scenario_is_built_on_the_next_run() {
+    let mut app = default_range();
+    let server = app.world.resource::<AssetServer>();
+    let scenarios = &app.world.resource::<ScenarioAssets>().scenarios;
+    let mut paths: Vec<String> = scenarios
+        .iter()
+        .map(|scenario| server.get_path(scenario.id()).unwrap().to_string())
+        .collect();
+    paths.sort();
+    assert_eq!(
+        paths,
+        [
+            "scenarios/default.scenario.ron",
+            "scenarios/rock.scenario.ron"
+        ]
+    );
+    let rock_only = scenarios
+        .iter()
+        .find(|scenario| server.get_path(scenario.id()).unwrap().to_string() == paths[1])
+        .unwrap()
+        .clone();
+
+    app.world.send_event(RunCommand::Menu);
+    update_until(&mut app, 10, |app| state(app) == State::Menu);
+    app.world.insert_resource(SelectedScenario(rock_only));
+    app.world.send_event(RunCommand::Start);
+    update_until(&mut app, 10, |app| state(app) == State::Play);
+    assert_eq!(count::<With<Health>>(&mut app), 1);
+    assert_eq!(count::<With<Launcher>>(&mut app), 0);
+}
@@ -0,0 +1,217 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+use bevy_xpbd_test::health::Health;
+use bevy_xpbd_test::menu::MenuPlugin;
+use bevy_xpbd_test::pool::Parked;
+use bevy_xpbd_test::pool::Pool;
+use bevy_xpbd_test::projectile::Launcher;
+use bevy_xpbd_test::projectile::Projectile;
+use bevy_xpbd_test::projectile::ProjectilePlugin;
+use bevy_xpbd_test::session::RunCommand;
+use bevy_xpbd_test::session::StateScoped;
+use bevy_xpbd_test::ShootingRangePlugin;
+use bevy_xpbd_test::State;
+use bevy_xpbd_test::Stats;
+use common::*;
+
+/// A range with a fast launcher.
+fn fast_launcher() -> ShootingRangePlugin {
+    ShootingRangePlugin {
+        projectile: ProjectilePlugin {
+            default_launcher: Some(Launcher {
+                fire_rate: 4.0,
+                ..default()
+            }),
+            ..default()
+        },
+        ..default()
+    }
+}
+
+/// A run with a fast launcher shooting at a rock with `health`.
+fn range(health: f32) -> App {
+    let mut app = headless_app(fast_launcher());
+    update_until_playing(&mut app);
+    *app.world.query::<&mut Health>().single_mut(&mut app.world) = Health::new(health);
+    app
+}
+
+fn shots(app: &App) -> usize {
+    app.world.resource::<Stats>().shots_fired
+}
+
+#[test]
+fn pausing_freezes_physics_and_launchers() {
+    let mut app = range(f32::MAX);
+    update_until(&mut app, 300, |app| shots(app) >= 2);
+
+    app.world.send_event(RunCommand::TogglePause);
+    update_until(&mut app, 10, |app| state(app) == State::Paused);
+    assert!(app.world.resource::<Time<Physics>>().is_paused());
+
+    let elapsed = app.world.resource::<Time<Physics>>().elapsed();
+    let fired = shots(&app);
+    let mut in_flight = app
+        .world
+        .query_filtered::<&Transform, (With<Projectile>, Without<Parked>)>();
+    let positions: Vec<Vec3> = in_flight.iter(&app.world).map(|t| t.translation).collect();
+    assert!(!positions.is_empty());
+    for _ in 0..120 {
+        app.update();
+    }
+    assert_eq!(app.world.resource::<Time<Physics>>().elapsed(), elapsed);
+    assert_eq!(shots(&app), fired);
+    let after: Vec<Vec3> = in_flight.iter(&app.world).map(|t| t.translation).collect();
+    assert_eq!(after, positions);
+
+    app.world.send_event(RunCommand::TogglePause);
+    update_until(&mut app, 120, |app| shots(app) > fired);
+    assert_eq!(state(&app), State::Play);
+    assert!(!app.world.resource::<Time<Physics>>().is_paused());
+}
+
+#[test]
+fn restarting_replaces_everything_spawned_in_the_run() {
+    let mut app = range(f32::MAX);
+    update_until(&mut app, 300, |app| shots(app) >= 3);
+    let old: Vec<Entity> = app
+        .world
+        .query_filtered::<Entity, With<StateScoped>>()
+        .iter(&app.world)
+        .collect();
+
+    app.world.send_event(RunCommand::Restart);
+    update_until(&mut app, 10, |app| {
+        state(app) == State::Play && app.world.get_entity(old[0]).is_none()
+    });
+    for entity in old {
+        assert!(app.world.get_entity(entity).is_none(), "{entity:?} left");
+    }
+    assert_eq!(shots(&app), 0);
+    assert_eq!(count::<With<Health>>(&mut app), 1);
+    assert_eq!(count::<With<Launcher>>(&mut app), 1);
+    assert_eq!(count::<With<Projectile>>(&mut app), 8);
+}
+
+#[test]
+fn going_to_the_menu_clears_the_range() {
+    let mut app = range(f32::MAX);
+    update_until(&mut app, 300, |app| shots(app) >= 3);
+    app.world.send_event(RunCommand::TogglePause);
+    update_until(&mut app, 10, |app| state(app) == State::Paused);
+
+    app.world.send_event(RunCommand::Menu);
+    update_until(&mut app, 10, |app| state(app) == State::Menu);
+    // Menus don't start runs on their own, unlike loading.
+    for _ in 0..10 {
+        app.update();
+    }
+    assert_eq!(state(&app), State::Menu);
+    assert!(!app.world.resource::<Time<Physics>>().is_paused());
+    assert_eq!(count::<With<StateScoped>>(&mut app), 0);
+    assert_eq!(count::<With<Health>>(&mut app), 0);
+    assert!(app.world.resource::<Pool<Projectile>>().is_empty());
+
+    app.world.send_event(RunCommand::Start);
+    update_until(&mut app, 10, |app| state(app) == State::Play);
+    assert_eq!(count::<With<Health>>(&mut app), 1);
+}
+
+#[test]
+fn destroying_every_target_shows_the_results() {
+    let mut app = range(1.0);
+    update_until(&mut app, 600, |app| state(app) == State::Results);
+    assert_eq!(count::<With<Health>>(&mut app), 0);
+
+    // The range stays as it was, without firing.
+    let fired = shots(&app);
+    for _ in 0..60 {
+        app.update();
+    }
+    assert_eq!(shots(&app), fired);
+    assert_eq!(count::<With<Launcher>>(&mut app), 1);
+}
+
+#[test]
+fn pausing_and_results_are_within_play() {
+    assert!(State::Paused.is_within(&State::Play));
+    assert!(State::Results.is_within(&State::Play));
+    assert!(State::Play.is_within(&State::Play));
+    assert!(!State::Menu.is_within(&State::Play));
+    assert!(!State::Play.is_within(&State::Paused));
+}
+
+#[test]
+fn entity_count_returns_to_baseline_after_every_run() {
+    let mut app = range(f32::MAX);
+    let entities = |app: &App| app.world.entities().len();
+    app.world.send_event(RunCommand::Menu);
+    update_until(&mut app, 10, |app| state(app) == State::Menu);
+    app.update();
+    let baseline = entities(&app);
+
+    for _ in 0..3 {
+        app.world.send_event(RunCommand::Start);
+        update_until(&mut app, 10, |app| state(app) == State::Play);
+        update_until(&mut app, 300, |app| shots(app) >= 3);
+        assert!(entities(&app) > baseline);
+        for command in [RunCommand::TogglePause, RunCommand::TogglePause] {
+            app.world.send_event(command);
+            app.update();
+            app.update();
+        }
+        app.world.send_event(RunCommand::Menu);
+        update_until(&mut app, 10, |app| state(app) == State::Menu);
+        app.update();
+        assert_eq!(entities(&app), baseline);
+        assert_eq!(count::<With<StateScoped>>(&mut app), 0);
+    }
+}
+
+#[test]
+fn restarting_passes_the_menu_without_showing_it() {
+    let mut app = headless_app(fast_launcher());
+    app.init_resource::<ButtonInput<KeyCode>>()
+        .add_plugins(MenuPlugin::default());
+    finish(&mut app);
+    let mut shown = 0;
+    // Autostarting from loading doesn't show it either.
+    update_until(&mut app, 100_000, |app| {
+        shown = shown.max(count::<With<Button>>(app));
+        state(app) == State::Play
+    });
+    update_until(&mut app, 300, |app| shots(app) >= 1);
+
+    app.world.send_event(RunCommand::Restart);
+    update_until(&mut app, 10, |app| {
+        shown = shown.max(count::<With<Button>>(app));
+        state(app) == State::Play && shots(app) == 0
+    });
+    assert_eq!(shown, 0);
+
+    app.world.send_event(RunCommand::Menu);
+    update_until(&mut app, 10, |app| state(app) == State::Menu);
+    app.update();
+    assert_eq!(count::<With<Button>>(&mut app), 2);
+}
+
+#[test]
+fn every_state_is_listed_for_cleanup() {
+    // Scoped entities are only despawned on leaving states in `State::ALL`.
+    // Walks every state in turn, and stops compiling when one is added.
+    let next = |state: State| match state {
+        State::Load => Some(State::LoadFailed),
+        State::LoadFailed => Some(State::Menu),
+        State::Menu => Some(State::Play),
+        State::Play => Some(State::Paused),
+        State::Paused => Some(State::Results),
+        State::Results => None,
+    };
+    let mut state = Some(State::Load);
+    while let Some(current) = state {
+        assert!(State::ALL.contains(&current), "{current:?}");
+        state = next(current);
+    }
+}
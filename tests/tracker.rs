@@ -0,0 +1,87 @@
+mod common;
+
+use std::time::Duration;
+
+use bevy::ecs::event::ManualEventReader;
+use bevy::prelude::*;
+use bevy_xpbd_test::marker::ContactGlow;
+use bevy_xpbd_test::tracker::*;
+use common::*;
+
+fn range() -> (App, Entity, Entity) {
+    let mut app = headless_app(no_launcher());
+    update_until_playing(&mut app);
+
+    let (rock, ammo) = rock_and_ammo(&mut app);
+    (app, rock, ammo)
+}
+
+fn read<E: Event + Clone>(app: &App, reader: &mut ManualEventReader<E>) -> Vec<E> {
+    reader
+        .read(app.world.resource::<Events<E>>())
+        .cloned()
+        .collect()
+}
+
+fn steps(n: u32) -> Duration {
+    Duration::from_secs_f64(n as f64 / 60.0)
+}
+
+fn assert_near(duration: Duration, expected: Duration) {
+    let error = duration.as_secs_f64() - expected.as_secs_f64();
+    assert!(error.abs() < 1e-3, "{duration:?}, expected {expected:?}");
+}
+
+#[test]
+fn pairs_enter_stay_and_exit() {
+    let (mut app, rock, ammo) = range();
+    let bodies = (rock.min(ammo), rock.max(ammo));
+    let mut entered = ManualEventReader::<ContactEntered>::default();
+    let mut stayed = ManualEventReader::<ContactStayed>::default();
+    let mut exited = ManualEventReader::<ContactExited>::default();
+
+    app.world.send_event(collision_started(rock, ammo));
+    app.update();
+    assert_eq!(read(&app, &mut entered), [ContactEntered { bodies }]);
+    assert!(app.world.resource::<ContactTracker>().is_touching(rock));
+
+    for n in 1..=2 {
+        // Whichever way round the colliders are reported.
+        app.world.send_event(collision_started(ammo, rock));
+        app.update();
+        let stays = read(&app, &mut stayed);
+        assert_eq!(stays.len(), 1);
+        assert_eq!(stays[0].bodies, bodies);
+        assert_near(stays[0].duration, steps(n));
+    }
+    assert!(read(&app, &mut entered).is_empty());
+
+    app.update();
+    let exits = read(&app, &mut exited);
+    assert_eq!(exits.len(), 1);
+    assert_near(exits[0].duration, steps(3));
+    let tracker = app.world.resource::<ContactTracker>();
+    assert!(!tracker.is_touching(rock));
+    assert_eq!(tracker.duration(rock, ammo), None);
+}
+
+#[test]
+fn glow_lasts_while_touched() {
+    let (mut app, rock, ammo) = range();
+    app.world.entity_mut(rock).insert(ContactGlow::default());
+
+    let mut lights = Vec::new();
+    for _ in 0..3 {
+        app.world.send_event(collision_started(rock, ammo));
+        app.update();
+        lights.push(app.world.get::<ContactGlow>(rock).unwrap().light());
+    }
+    let light = lights[0].unwrap();
+    assert!(lights.iter().all(|l| *l == Some(light)));
+    assert!(app.world.get::<PointLight>(light).is_some());
+    assert_eq!(app.world.get::<Parent>(light).unwrap().get(), rock);
+
+    app.update();
+    assert_eq!(app.world.get::<ContactGlow>(rock).unwrap().light(), None);
+    assert!(app.world.get_entity(light).is_none());
+}